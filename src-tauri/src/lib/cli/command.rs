@@ -0,0 +1,353 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::{borrow::Cow, collections::HashMap, process::ExitCode, sync::Arc};
+
+use super::helper::*;
+use super::typedef::*;
+use crate::engine::forecast::{backtest, predict, write_prediction};
+use crate::engine::preprocess::*;
+use crate::engine::source::*;
+use crate::engine::storage::{load_model, save_model};
+use crate::engine::train::train;
+use crate::engine::typedef::{ColumnInfo, ColumnType, ErrorInfo, PreprocessConfig, TrainControl};
+
+const ERROR_COLUMN: &'static str = "Column Unusable";
+
+pub(crate) fn run() -> ExitCode {
+	let cli_args = match parse_args(std::env::args().skip(1)) {
+		Ok(Some(found)) => found,
+		Ok(None) => {
+			println!("{}", USAGE);
+			return ExitCode::SUCCESS;
+		}
+		Err(err) => {
+			eprintln!("{}\n\n{}", err, USAGE);
+			return ExitCode::FAILURE;
+		}
+	};
+
+	match run_pipeline(cli_args) {
+		Ok(_) => ExitCode::SUCCESS,
+		Err(err) => {
+			eprintln!("{}", err);
+			ExitCode::FAILURE
+		}
+	}
+}
+
+/// Same load → preprocess → train → predict steps as the GUI, without any dialog.
+fn run_pipeline(cli_args: CliArgs) -> Result<(), ErrorInfo> {
+	eprintln!("Loading {}", cli_args.input_path.to_string_lossy());
+
+	let mut source_data = open_source_data(&cli_args.input_path, &cli_args.csv_dialect)?;
+	let tabs = list_tabs(&source_data)?;
+	let tab_name = match (&tabs, cli_args.tab_name) {
+		(None, _) => String::new(),
+		(Some(_), Some(found)) => found,
+		(Some(tabs), None) => tabs.first().cloned().unwrap_or_default(),
+	};
+
+	let loaded_model = match &cli_args.model_path {
+		Some(found) => {
+			eprintln!("Loading model {}", found.to_string_lossy());
+			Some(load_model(found)?)
+		}
+		None => None,
+	};
+
+	// Date/time pattern needs to know which column it belongs to
+	let mut datetime_format = cli_args.datetime_format;
+	datetime_format.column = match (&cli_args.datetime_column, &loaded_model) {
+		(Some(found), _) => Some(Arc::new(found.to_lowercase())),
+		(None, Some(model)) => Some(model.datetime_column.clone()),
+		(None, None) if datetime_format.format.is_some() || datetime_format.numeric.is_some() => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_COLUMN),
+				message: String::from(
+					"The --datetime-format and --timestamp-unit need --datetime-column as well",
+				),
+			})
+		}
+		(None, None) => None,
+	};
+
+	let content = read_sheet(
+		&mut source_data,
+		&tab_name,
+		&datetime_format,
+		&cli_args.column_types,
+		&cli_args.sheet_layout,
+		&cli_args.missing_values,
+	)?;
+	if let Some(warning) = &content.warning {
+		eprintln!("{}", warning);
+	}
+
+	// Tell which columns drop rows, so the type can be chosen with --column-type
+	for each in content
+		.column_inferences
+		.iter()
+		.filter(|each| each.offending_count > 0)
+	{
+		let samples = each
+			.offending_samples
+			.iter()
+			.map(|sample| format!("row {} \"{}\"", sample.row_id, sample.value))
+			.collect::<Vec<_>>()
+			.join(", ");
+		eprintln!(
+			"Column \"{}\" has {} unmatched cells, e.g. {}",
+			each.field, each.offending_count, samples
+		);
+	}
+
+	// Loaded model prefers the same columns it was trained with
+	let datetime_column = match (cli_args.datetime_column, &loaded_model) {
+		(Some(found), _) => pick_column(&content.columns, &found, ColumnType::DATETIME)?,
+		(None, Some(model)) => pick_column(
+			&content.columns,
+			&model.datetime_column,
+			ColumnType::DATETIME,
+		)?,
+		(None, None) => content.selected_datetime_column.clone(),
+	};
+
+	let predictable_column = match (cli_args.predictable_column, &loaded_model) {
+		(Some(found), _) => pick_column(&content.columns, &found, ColumnType::NUMBER)?,
+		(None, Some(model)) => pick_column(
+			&content.columns,
+			&model.predictable_column,
+			ColumnType::NUMBER,
+		)?,
+		(None, None) => content.selected_predictable_column.clone(),
+	};
+
+	let requested_target_columns = match &loaded_model {
+		Some(model) if cli_args.target_columns.is_empty() => model
+			.target_columns
+			.iter()
+			.map(|each| each.to_string())
+			.collect::<Vec<_>>(),
+		_ => cli_args.target_columns,
+	};
+	let target_columns = requested_target_columns
+		.iter()
+		.map(|each| pick_column(&content.columns, each, ColumnType::NUMBER))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let requested_feature_columns = match &loaded_model {
+		Some(model) if cli_args.feature_columns.is_empty() => model
+			.feature_columns
+			.iter()
+			.map(|each| each.to_string())
+			.collect::<Vec<_>>(),
+		_ => cli_args.feature_columns,
+	};
+	let feature_columns = requested_feature_columns
+		.iter()
+		.map(|each| pick_column(&content.columns, each, ColumnType::NUMBER))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let series_column = match (cli_args.series_column, &loaded_model) {
+		(Some(found), _) => Some(pick_series_column(&content.columns, &found)?),
+		(None, Some(model)) => match &model.series_column {
+			Some(found) => Some(pick_series_column(&content.columns, found)?),
+			None => None,
+		},
+		(None, None) => None,
+	};
+
+	let allowed_batch_periodes = decide_allowed_batch_periodes(&content.columns, &content.rows);
+	let requested_batch_periode = cli_args
+		.batch_periode
+		.or(loaded_model.as_ref().map(|model| model.batch_periode));
+	let batch_periode = match requested_batch_periode {
+		Some(found) if allowed_batch_periodes.contains(&found) => found,
+		Some(found) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed("Batch Periode Unsupported"),
+				message: format!(
+					"Because the data is too sparse to be batched {}, try one of: {}",
+					found,
+					allowed_batch_periodes
+						.iter()
+						.map(|each| each.to_string())
+						.collect::<Vec<_>>()
+						.join(", ")
+				),
+			})
+		}
+		None => allowed_batch_periodes
+			.get(1)
+			.or(allowed_batch_periodes.first())
+			.cloned()
+			.unwrap_or_default(),
+	};
+
+	let config = PreprocessConfig {
+		tab_name: tabs.and(Some(tab_name)),
+		datetime_column,
+		predictable_column,
+		target_columns,
+		feature_columns,
+		series_column,
+		batch_periode,
+		row_selection: Default::default(),
+		datetime_format: content.datetime_format.clone(),
+		column_types: cli_args.column_types,
+		sheet_layout: cli_args.sheet_layout,
+		missing_values: cli_args.missing_values,
+		duplicate_aggregation: cli_args.duplicate_aggregation,
+	};
+
+	eprintln!(
+		"Re-sampling \"{}\" over \"{}\" {}",
+		config
+			.value_columns()
+			.iter()
+			.map(|each| each.as_str())
+			.collect::<Vec<_>>()
+			.join("\", \""),
+		config.datetime_column,
+		config.batch_periode
+	);
+
+	let mut selected_source_data = extract_series(
+		&mut source_data,
+		&content.column_lookup,
+		&content.dropped_row_indices,
+		&config,
+	)?;
+	let imputation_summaries = impute_missing_values(&mut selected_source_data, &config);
+	if let Some(warning) = imputation_warning(&imputation_summaries) {
+		eprintln!("{}", warning);
+	}
+
+	let merged_count =
+		aggregate_duplicate_timestamps(&mut selected_source_data, config.duplicate_aggregation);
+	if let Some(warning) = aggregation_warning(merged_count, config.duplicate_aggregation) {
+		eprintln!("{}", warning);
+	}
+
+	if config.series_column.is_some() {
+		eprintln!("Found {} series", selected_source_data.len());
+	}
+
+	let mut progress = StderrProgress {
+		end_x: cli_args.train_settings.max_epochs,
+		phase: "Predicting",
+	};
+
+	let (trained_model, preprocessed_data) = match loaded_model {
+		Some(model) => {
+			let preprocessed_data =
+				build_historical_data_like(selected_source_data, &config, &model)?;
+			(model, preprocessed_data)
+		}
+		None => {
+			let preprocessed_data = build_historical_data(selected_source_data, &config)?;
+			let trained_model = train(
+				&preprocessed_data,
+				&cli_args.train_settings,
+				&TrainControl::default(),
+				&mut progress,
+			)?;
+			(trained_model, preprocessed_data)
+		}
+	};
+
+	if let Some(found) = &cli_args.save_model_path {
+		save_model(&trained_model, found)?;
+		eprintln!("Model saved into {}", found.to_string_lossy());
+	}
+
+	let forecast = predict(
+		&trained_model,
+		&preprocessed_data,
+		cli_args.horizon.as_ref(),
+		&mut progress,
+	)?;
+	eprintln!("\rPredicting 100%");
+
+	if let Some(settings) = &cli_args.backtest {
+		progress.phase = "Backtesting";
+		let result = backtest(&trained_model, &preprocessed_data, settings, &mut progress)?;
+		eprintln!("\rBacktesting 100%");
+
+		for each in &result.origins {
+			match &each.series_id {
+				Some(found) => eprintln!(
+					"Backtest \"{}\" from {}: {}",
+					found,
+					each.x.to_rfc3339(),
+					each.metrics
+				),
+				None => eprintln!("Backtest from {}: {}", each.x.to_rfc3339(), each.metrics),
+			}
+		}
+		eprintln!(
+			"Backtest overall, within the last {} steps held out from training: {}",
+			result.held_out, result.metrics
+		);
+	}
+
+	let column_units = content
+		.columns
+		.iter()
+		.filter_map(|each| Some((each.field.clone(), each.unit.clone()?)))
+		.collect::<HashMap<_, _>>();
+	write_prediction(&cli_args.output_path, &forecast, &column_units)?;
+	eprintln!("Saved into {}", cli_args.output_path.to_string_lossy());
+
+	Ok(())
+}
+
+fn pick_column(
+	columns: &[ColumnInfo],
+	name: &str,
+	expected_type: ColumnType,
+) -> Result<Arc<String>, ErrorInfo> {
+	let field = name.to_lowercase();
+	match columns.iter().find(|each| *each.field == field) {
+		Some(found) if found.column_type == expected_type => Ok(found.field.clone()),
+		Some(found) => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_COLUMN),
+			message: format!(
+				"Because \"{}\" column contains {:?} instead of {:?}",
+				found.header_name, found.column_type, expected_type
+			),
+		}),
+		None => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_COLUMN),
+			message: format!("Because \"{}\" column does not exist", name),
+		}),
+	}
+}
+
+/// Same as [`pick_column`], but any type of column can tell the series apart
+fn pick_series_column(columns: &[ColumnInfo], name: &str) -> Result<Arc<String>, ErrorInfo> {
+	let field = name.to_lowercase();
+	match columns.iter().find(|each| *each.field == field) {
+		Some(found) => Ok(found.field.clone()),
+		None => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_COLUMN),
+			message: format!("Because \"{}\" column does not exist", name),
+		}),
+	}
+}
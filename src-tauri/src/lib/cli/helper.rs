@@ -0,0 +1,498 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::{borrow::Cow, path::PathBuf, sync::Arc};
+use strum::IntoEnumIterator;
+
+use super::typedef::*;
+use crate::engine::storage::MODEL_EXTENSION;
+use crate::engine::typedef::{
+	BatchPeriode, ColumnType, DuplicateAggregation, ErrorInfo, ForecastHorizon, HeaderCombine,
+	MissingValueStrategy, NumericTimestamp, TrainSettings,
+};
+
+const ERROR_ARGUMENT: &'static str = "Invalid Argument";
+
+pub(super) const USAGE: &'static str = "\
+Usage: tarot-cli --input <FILE> --output <FILE> [OPTIONS]
+
+Loads historical data, re-samples it, trains a new LSTM model (or loads a saved one), then writes
+the historical and predicted data of each predicted column into a CSV file. Progress is printed to
+stderr.
+
+Options:
+  -i, --input <FILE>               CSV, spreadsheet, Parquet, Arrow, (ND)JSON, or SQLite file
+  -o, --output <FILE>              Where the predicted data will be saved as CSV, .csv is added
+                                   if the file has no extension
+  -t, --tab <NAME>                 Worksheet or table name, defaults to the first one
+  -q, --query <SQL>                Custom SELECT query instead of a whole SQLite table
+  -D, --delimiter <CHAR>           CSV delimiter such as \",\", \";\", or \"tab\", sniffed if unset
+      --quote <CHAR>               CSV quote character, sniffed if unset
+      --encoding <LABEL>           CSV encoding such as utf-8 or windows-1252, sniffed if unset
+      --has-header <BOOL>          Whether the first CSV row is the header, sniffed if unset
+      --decimal-comma <BOOL>       Whether CSV numbers are written like 1.234,56, sniffed if unset
+      --header-row <ROW>           Worksheet row of the header, counted from its first non-empty
+                                   row, defaults to 1
+      --header-rows <COUNT>        Number of worksheet header rows, defaults to 1
+      --skip-rows <COUNT>          Worksheet rows between the header and the data, defaults to 0
+      --header-combine <HOW>       One of join, first, or last, how multi-row header becomes one
+      --header-separator <TEXT>    Between the joined header rows, defaults to a space
+  -d, --datetime-column <NAME>     Date/time column, defaults to the first one found
+      --datetime-format <PATTERN>  strftime pattern of the date/time column such as %d/%m/%Y
+      --timezone <ZONE>            IANA time zone such as Europe/Berlin or UTC, defaults to local
+      --timestamp-unit <UNIT>      Date/time column is a number of seconds, milliseconds,
+                                   microseconds, or excel (serial days), detected if unset
+                                   only when the column is named like a time
+      --column-type <NAME=TYPE>    Type of a column instead of the majority of its cells, one of
+                                   string, number, datetime, or boolean, can be repeated
+      --fill-missing <NAME=HOW>    Fills missing or invalid cells of a number column instead of
+                                   dropping their rows, one of drop, forward, backward, linear,
+                                   mean, or constant:VALUE, can be repeated
+  -p, --predictable-column <NAME>  Column to be predicted, defaults to the first number column
+  -T, --target-column <NAME>       Another column to be predicted together, can be repeated
+  -f, --feature-column <NAME>      Extra number column fed into the model, can be repeated
+  -g, --series-column <NAME>       Column which splits rows into separate series, if any
+  -b, --periode <PERIODE>          One of minutely, hourly, daily, weekly, monthly, or yearly
+      --duplicates <HOW>           How rows sharing a timestamp are merged, one of mean, sum,
+                                   first, last, min, or max, defaults to mean
+  -m, --model <FILE>               Forecast with a saved model instead of training a new one
+  -s, --save-model <FILE>          Where the trained model will be saved
+  -c, --train-settings <FILE>      JSON file of training settings, unset fields use the default
+  -B, --backtest <STEPS>           Also hide the last STEPS re-sampled steps and forecast them,
+                                   all hidden steps must be within the validation split
+  -O, --backtest-origins <COUNT>   Number of rolling origins to backtest, defaults to 1
+  -H, --horizon <COUNT>            How far to forecast, in re-sampled steps unless a unit is given
+  -u, --horizon-unit <PERIODE>     Unit of --horizon, e.g. -H 30 -u daily means the next 30 days
+  -h, --help                       Print this help message";
+
+/// Returns `Ok(None)` when user only asks for the help message.
+pub(super) fn parse_args(
+	mut args: impl Iterator<Item = String>,
+) -> Result<Option<CliArgs>, ErrorInfo> {
+	let mut input_path = Option::<PathBuf>::None;
+	let mut output_path = Option::<PathBuf>::None;
+	let mut horizon_count = Option::<usize>::None;
+	let mut horizon_unit = Option::<BatchPeriode>::None;
+	let mut cli_args = CliArgs::default();
+
+	while let Some(flag) = args.next() {
+		if flag == "-h" || flag == "--help" {
+			return Ok(None);
+		}
+
+		let value = match args.next() {
+			Some(found) => found,
+			None => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_ARGUMENT),
+					message: format!("Missing value after \"{}\"", flag),
+				})
+			}
+		};
+
+		match flag.as_str() {
+			"-i" | "--input" => input_path = Some(PathBuf::from(value)),
+			"-o" | "--output" => output_path = Some(PathBuf::from(value)),
+			"-t" | "--tab" | "-q" | "--query" => cli_args.tab_name = Some(value),
+			"-D" | "--delimiter" => {
+				cli_args.csv_dialect.delimiter = Some(parse_char(&flag, &value)?)
+			}
+			"--quote" => cli_args.csv_dialect.quote = Some(parse_char(&flag, &value)?),
+			"--encoding" => cli_args.csv_dialect.encoding = Some(value),
+			"--has-header" => cli_args.csv_dialect.has_header = Some(parse_bool(&flag, &value)?),
+			"--decimal-comma" => {
+				cli_args.csv_dialect.decimal_comma = Some(parse_bool(&flag, &value)?)
+			}
+			"--header-row" => cli_args.sheet_layout.header_row = parse_count(&flag, &value)? - 1,
+			"--header-rows" => cli_args.sheet_layout.header_rows = parse_count(&flag, &value)?,
+			"--skip-rows" => cli_args.sheet_layout.skip_rows = parse_skip(&flag, &value)?,
+			"--header-combine" => {
+				cli_args.sheet_layout.header_combine = parse_header_combine(&value)?
+			}
+			"--header-separator" => cli_args.sheet_layout.header_separator = value,
+			"-d" | "--datetime-column" => cli_args.datetime_column = Some(value),
+			"--datetime-format" => cli_args.datetime_format.format = Some(value),
+			"--timezone" => cli_args.datetime_format.timezone = Some(value),
+			"--column-type" => {
+				let (name, column_type) = parse_column_type(&value)?;
+				cli_args.column_types.insert(name, column_type);
+			}
+			"--fill-missing" => {
+				let (name, strategy) = parse_missing_value(&value)?;
+				cli_args.missing_values.insert(name, strategy);
+			}
+			"--timestamp-unit" => {
+				cli_args.datetime_format.numeric = Some(parse_numeric_timestamp(&value)?)
+			}
+			"-p" | "--predictable-column" => cli_args.predictable_column = Some(value),
+			"-T" | "--target-column" => cli_args.target_columns.push(value),
+			"-f" | "--feature-column" => cli_args.feature_columns.push(value),
+			"-g" | "--series-column" => cli_args.series_column = Some(value),
+			"-b" | "--periode" => cli_args.batch_periode = Some(parse_batch_periode(&value)?),
+			"--duplicates" => cli_args.duplicate_aggregation = parse_duplicate_aggregation(&value)?,
+			"-m" | "--model" => cli_args.model_path = Some(PathBuf::from(value)),
+			"-c" | "--train-settings" => cli_args.train_settings = read_train_settings(&value)?,
+			"-B" | "--backtest" => {
+				cli_args.backtest.get_or_insert_default().holdout = parse_count(&flag, &value)?
+			}
+			"-O" | "--backtest-origins" => {
+				cli_args.backtest.get_or_insert_default().origins = parse_count(&flag, &value)?
+			}
+			"-H" | "--horizon" => horizon_count = Some(parse_count(&flag, &value)?),
+			"-u" | "--horizon-unit" => horizon_unit = Some(parse_batch_periode(&value)?),
+			"-s" | "--save-model" => {
+				// Keep the extension picked by user, same as --output
+				let found = PathBuf::from(value);
+				cli_args.save_model_path = match found.extension() {
+					Some(_) => Some(found),
+					None => Some(found.with_extension(MODEL_EXTENSION)),
+				}
+			}
+			_ => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_ARGUMENT),
+					message: format!("Unknown option \"{}\"", flag),
+				})
+			}
+		}
+	}
+
+	cli_args.horizon = match (horizon_count, horizon_unit) {
+		(Some(count), None) => Some(ForecastHorizon::Steps { count }),
+		(Some(count), Some(unit)) => match u32::try_from(count) {
+			Ok(amount) => Some(ForecastHorizon::Duration { amount, unit }),
+			Err(_) => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_ARGUMENT),
+					message: format!("The --horizon {} is too large for --horizon-unit", count),
+				})
+			}
+		},
+		(None, Some(_)) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: String::from("The --horizon-unit needs --horizon as well"),
+			})
+		}
+		(None, None) => None,
+	};
+
+	cli_args.input_path = match input_path {
+		Some(found) => found,
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: String::from("The --input file is required"),
+			})
+		}
+	};
+
+	cli_args.output_path = match output_path {
+		// Keep the extension picked by user, e.g. ".tsv" or ".txt"
+		Some(found) if found.extension().is_some() => found,
+		Some(found) => found.with_extension("csv"),
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: String::from("The --output file is required"),
+			})
+		}
+	};
+
+	Ok(Some(cli_args))
+}
+
+fn parse_batch_periode(value: &str) -> Result<BatchPeriode, ErrorInfo> {
+	let value = value.to_lowercase();
+	match BatchPeriode::iter().find(|each| each.to_string() == value) {
+		Some(found) => Ok(found),
+		None => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!("Cannot parse \"{}\" as batch periode", value),
+		}),
+	}
+}
+
+fn parse_duplicate_aggregation(value: &str) -> Result<DuplicateAggregation, ErrorInfo> {
+	match value.to_lowercase().as_str() {
+		"mean" => Ok(DuplicateAggregation::Mean),
+		"sum" => Ok(DuplicateAggregation::Sum),
+		"first" => Ok(DuplicateAggregation::First),
+		"last" => Ok(DuplicateAggregation::Last),
+		"min" => Ok(DuplicateAggregation::Min),
+		"max" => Ok(DuplicateAggregation::Max),
+		_ => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!("Cannot parse \"{}\" as duplicate aggregation", value),
+		}),
+	}
+}
+
+fn parse_numeric_timestamp(value: &str) -> Result<NumericTimestamp, ErrorInfo> {
+	match value.to_lowercase().as_str() {
+		"s" | "seconds" => Ok(NumericTimestamp::Seconds),
+		"ms" | "milliseconds" => Ok(NumericTimestamp::Milliseconds),
+		"us" | "microseconds" => Ok(NumericTimestamp::Microseconds),
+		"excel" => Ok(NumericTimestamp::ExcelSerial),
+		_ => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!("Cannot parse \"{}\" as timestamp unit", value),
+		}),
+	}
+}
+
+fn parse_header_combine(value: &str) -> Result<HeaderCombine, ErrorInfo> {
+	match value.to_lowercase().as_str() {
+		"join" => Ok(HeaderCombine::Join),
+		"first" => Ok(HeaderCombine::First),
+		"last" => Ok(HeaderCombine::Last),
+		_ => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!("Cannot parse \"{}\" as header combination", value),
+		}),
+	}
+}
+
+fn parse_column_type(value: &str) -> Result<(Arc<String>, ColumnType), ErrorInfo> {
+	let (name, column_type) = match value.rsplit_once('=') {
+		Some(found) => found,
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: format!(
+					"Expected NAME=TYPE after \"--column-type\", got \"{}\"",
+					value
+				),
+			})
+		}
+	};
+
+	let column_type = match column_type.trim().to_lowercase().as_str() {
+		"string" | "text" => ColumnType::STRING,
+		"number" => ColumnType::NUMBER,
+		"datetime" => ColumnType::DATETIME,
+		"boolean" => ColumnType::BOOLEAN,
+		_ => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: format!("Cannot parse \"{}\" as column type", column_type),
+			})
+		}
+	};
+
+	Ok((Arc::new(name.trim().to_lowercase()), column_type))
+}
+
+fn parse_missing_value(value: &str) -> Result<(Arc<String>, MissingValueStrategy), ErrorInfo> {
+	let (name, strategy) = match value.rsplit_once('=') {
+		Some(found) => found,
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: format!(
+					"Expected NAME=HOW after \"--fill-missing\", got \"{}\"",
+					value
+				),
+			})
+		}
+	};
+
+	let strategy = strategy.trim().to_lowercase();
+	let strategy = match strategy.split_once(':') {
+		Some(("constant", constant)) => match constant.trim().parse::<f64>() {
+			Ok(ok) => MissingValueStrategy::Constant(ok),
+			Err(_) => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_ARGUMENT),
+					message: format!("Cannot parse \"{}\" as constant", constant),
+				})
+			}
+		},
+		_ => match strategy.as_str() {
+			"drop" => MissingValueStrategy::Drop,
+			"forward" | "ffill" => MissingValueStrategy::ForwardFill,
+			"backward" | "bfill" => MissingValueStrategy::BackwardFill,
+			"linear" => MissingValueStrategy::Linear,
+			"mean" => MissingValueStrategy::Mean,
+			_ => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_ARGUMENT),
+					message: format!("Cannot parse \"{}\" as missing value strategy", strategy),
+				})
+			}
+		},
+	};
+
+	Ok((Arc::new(name.trim().to_lowercase()), strategy))
+}
+
+fn parse_count(flag: &str, value: &str) -> Result<usize, ErrorInfo> {
+	match value.parse::<usize>() {
+		Ok(ok) if ok > 0 => Ok(ok),
+		_ => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!(
+				"Expected a positive number after \"{}\", got \"{}\"",
+				flag, value
+			),
+		}),
+	}
+}
+
+/// Same as [`parse_count`], but zero is allowed
+fn parse_skip(flag: &str, value: &str) -> Result<usize, ErrorInfo> {
+	match value.parse::<usize>() {
+		Ok(ok) => Ok(ok),
+		Err(_) => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!(
+				"Expected zero or a positive number after \"{}\", got \"{}\"",
+				flag, value
+			),
+		}),
+	}
+}
+
+fn parse_char(flag: &str, value: &str) -> Result<char, ErrorInfo> {
+	let mut chars = value.chars();
+	match (value, chars.next(), chars.next()) {
+		("tab" | "\\t", _, _) => Ok('\t'),
+		(_, Some(found), None) => Ok(found),
+		_ => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!(
+				"Expected a single character after \"{}\", got \"{}\"",
+				flag, value
+			),
+		}),
+	}
+}
+
+fn parse_bool(flag: &str, value: &str) -> Result<bool, ErrorInfo> {
+	match value.to_lowercase().as_str() {
+		"true" | "yes" | "1" => Ok(true),
+		"false" | "no" | "0" => Ok(false),
+		_ => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_ARGUMENT),
+			message: format!(
+				"Expected true or false after \"{}\", got \"{}\"",
+				flag, value
+			),
+		}),
+	}
+}
+
+fn read_train_settings(path: &str) -> Result<TrainSettings, ErrorInfo> {
+	let content = match std::fs::read_to_string(path) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: format!("Cannot read \"{}\": {}", path, err),
+			})
+		}
+	};
+
+	let settings: TrainSettings = match serde_json::from_str(&content) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_ARGUMENT),
+				message: format!("Cannot parse \"{}\": {}", path, err),
+			})
+		}
+	};
+
+	settings.validate()?;
+	Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(args: &[&str]) -> Result<Option<CliArgs>, ErrorInfo> {
+		parse_args(args.iter().map(|each| each.to_string()))
+	}
+
+	#[test]
+	fn input_and_output_are_required() {
+		let found = parse(&["-o", "forecast.csv"]).unwrap_err();
+		assert_eq!(found.message, "The --input file is required");
+
+		let found = parse(&["-i", "sales.csv"]).unwrap_err();
+		assert_eq!(found.message, "The --output file is required");
+
+		let found = parse(&["-i", "sales.csv", "-o"]).unwrap_err();
+		assert_eq!(found.message, "Missing value after \"-o\"");
+
+		let cli_args = parse(&["-i", "sales.csv", "-o", "forecast"])
+			.unwrap()
+			.unwrap();
+		assert_eq!(cli_args.input_path, PathBuf::from("sales.csv"));
+		assert_eq!(cli_args.output_path, PathBuf::from("forecast.csv"));
+		assert!(parse(&["-i", "sales.csv", "--help"]).unwrap().is_none());
+	}
+
+	#[test]
+	fn unknown_flag_is_rejected() {
+		let found = parse(&["-i", "sales.csv", "--bogus", "1"]).unwrap_err();
+		assert_eq!(found.message, "Unknown option \"--bogus\"");
+	}
+
+	#[test]
+	fn horizon_unit_needs_horizon() {
+		let base = ["-i", "sales.csv", "-o", "forecast.csv"];
+		let with = |extra: &[&str]| parse(&[&base[..], extra].concat());
+
+		let cli_args = with(&["-H", "12"]).unwrap().unwrap();
+		assert_eq!(cli_args.horizon, Some(ForecastHorizon::Steps { count: 12 }));
+
+		let cli_args = with(&["-H", "30", "-u", "daily"]).unwrap().unwrap();
+		assert_eq!(
+			cli_args.horizon,
+			Some(ForecastHorizon::Duration {
+				amount: 30,
+				unit: BatchPeriode::DAILY,
+			})
+		);
+
+		let found = with(&["-u", "daily"]).unwrap_err();
+		assert_eq!(found.message, "The --horizon-unit needs --horizon as well");
+		assert!(with(&["-H", "0"]).is_err());
+		assert!(with(&["-H", "30", "-u", "fortnightly"]).is_err());
+		assert!(with(&["-H", "99999999999", "-u", "daily"]).is_err());
+	}
+
+	#[test]
+	fn saved_model_keeps_its_extension() {
+		let base = ["-i", "sales.csv", "-o", "forecast.csv"];
+		let with = |extra: &[&str]| parse(&[&base[..], extra].concat()).unwrap().unwrap();
+
+		let cli_args = with(&["-s", "sales"]);
+		assert_eq!(
+			cli_args.save_model_path,
+			Some(PathBuf::from("sales.tarotmodel"))
+		);
+		let cli_args = with(&["-s", "sales.v2.bin"]);
+		assert_eq!(
+			cli_args.save_model_path,
+			Some(PathBuf::from("sales.v2.bin"))
+		);
+	}
+}
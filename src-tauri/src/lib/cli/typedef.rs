@@ -0,0 +1,81 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::{collections::HashMap, io::Write, path::PathBuf, sync::Arc};
+
+use crate::engine::progress::Progress;
+use crate::engine::typedef::{
+	BacktestSettings, BatchPeriode, ColumnType, CsvDialectOverride, DatetimeFormat,
+	DuplicateAggregation, ForecastHorizon, IterativePoint, MissingValueStrategy, SheetLayout,
+	StopReason, TrainSettings,
+};
+
+#[derive(Default, Clone, Debug)]
+pub(crate) struct CliArgs {
+	pub input_path: PathBuf,
+	pub output_path: PathBuf,
+	pub csv_dialect: CsvDialectOverride,
+	pub tab_name: Option<String>,
+	pub sheet_layout: SheetLayout, // Only for spreadsheet
+	pub datetime_column: Option<String>,
+	pub datetime_format: DatetimeFormat, // Column is only known after the sheet is read
+	pub column_types: HashMap<Arc<String>, ColumnType>,
+	pub missing_values: HashMap<Arc<String>, MissingValueStrategy>,
+	pub predictable_column: Option<String>,
+	pub target_columns: Vec<String>,
+	pub feature_columns: Vec<String>,
+	pub series_column: Option<String>,
+	pub batch_periode: Option<BatchPeriode>,
+	pub duplicate_aggregation: DuplicateAggregation,
+	pub model_path: Option<PathBuf>,
+	pub save_model_path: Option<PathBuf>,
+	pub train_settings: TrainSettings,
+	pub backtest: Option<BacktestSettings>,
+	pub horizon: Option<ForecastHorizon>,
+}
+
+/// Prints engine progress to stderr
+pub(super) struct StderrProgress {
+	pub end_x: u32,
+	pub phase: &'static str, // What on_predict_progress is counting, e.g. "Predicting"
+}
+
+impl Progress for StderrProgress {
+	fn on_train_progress(&mut self, point: IterativePoint) {
+		let errors = point
+			.metrics
+			.iter()
+			.map(|each| {
+				format!(
+					"{} MAE {:.4}, RMSE {:.4}",
+					each.column, each.metrics.mae, each.metrics.rmse
+				)
+			})
+			.collect::<Vec<_>>();
+		eprintln!("Epoch {}/{}: {}", point.x, self.end_x, errors.join("; "));
+	}
+
+	fn on_train_stop(&mut self, reason: StopReason, best_epoch: u32) {
+		eprintln!("Stopped because {}, keeping epoch {}", reason, best_epoch);
+	}
+
+	fn on_predict_progress(&mut self, percent: f64) {
+		eprint!("\r{} {:.0}%", self.phase, percent);
+		let _ = std::io::stderr().flush();
+	}
+}
@@ -20,6 +20,7 @@ use std::{borrow::Cow, sync::Mutex};
 use tauri::{AppHandle, Emitter, State};
 use tauri_plugin_dialog::{DialogExt, FilePath};
 
+use crate::engine::storage::MODEL_EXTENSION;
 use crate::typedef::{AppState, ErrorInfo};
 
 #[tauri::command]
@@ -30,14 +31,27 @@ pub(crate) async fn load_data(app: AppHandle, state: State<'_, Mutex<AppState>>)
 		.file()
 		.set_title("Choose a historical data file")
 		.add_filter(
-			"Supported Spreadsheet File",
-			&["csv", "xlsx", "xlsb", "xls", "ods"],
+			"Supported Historical Data File",
+			&[
+				"csv", "xlsx", "xlsb", "xls", "ods", "parquet", "arrow", "arrows", "feather",
+				"json", "ndjson", "jsonl", "sqlite", "sqlite3", "db", "db3",
+			],
 		)
 		.add_filter("Comma-Separated Values (CSV) File", &["csv"])
 		.add_filter("Microsoft Excel File", &["xlsx"])
 		.add_filter("Microsoft Excel Binary File", &["xlsb"])
 		.add_filter("Legacy Microsoft Excel File", &["xls"])
 		.add_filter("OpenDocument Spreadsheet (ODS) File", &["ods"])
+		.add_filter("Apache Parquet File", &["parquet"])
+		.add_filter(
+			"Apache Arrow IPC (Feather) File",
+			&["arrow", "arrows", "feather"],
+		)
+		.add_filter(
+			"JSON or Newline-Delimited JSON File",
+			&["json", "ndjson", "jsonl"],
+		)
+		.add_filter("SQLite Database", &["sqlite", "sqlite3", "db", "db3"])
 		.add_filter("Other File Type", &["*"])
 		.pick_file(move |file_path| {
 			let _ = tx.blocking_send(file_path);
@@ -83,6 +97,12 @@ pub(crate) async fn load_data(app: AppHandle, state: State<'_, Mutex<AppState>>)
 	};
 
 	guarded_state.source_path = Some(source_path);
+	guarded_state.csv_dialect = Default::default();
+	guarded_state.datetime_format = Default::default();
+	guarded_state.column_types = Default::default();
+	guarded_state.sheet_layout = Default::default();
+	guarded_state.missing_values = Default::default();
+	guarded_state.preprocess_config = None;
 	guarded_state.page_index = 1;
 
 	if let Err(err) = app.emit(crate::event::PAGE_MOVE, guarded_state.page_index) {
@@ -99,3 +119,95 @@ pub(crate) async fn load_data(app: AppHandle, state: State<'_, Mutex<AppState>>)
 
 	Ok(())
 }
+
+/// Returns file name of the loaded model, or `None` if user canceled
+#[tauri::command]
+pub(crate) async fn load_model(
+	app: AppHandle,
+	state: State<'_, Mutex<AppState>>,
+) -> Result<Option<String>, ()> {
+	let (tx, mut rx) = tauri::async_runtime::channel::<Option<FilePath>>(1024);
+
+	app.dialog()
+		.file()
+		.set_title("Choose a saved model")
+		.add_filter("Project::Tarot Model File", &[MODEL_EXTENSION])
+		.add_filter("Other File Type", &["*"])
+		.pick_file(move |file_path| {
+			let _ = tx.blocking_send(file_path);
+		});
+
+	let file_path_option = rx.recv().await;
+	if file_path_option
+		.as_ref()
+		.is_none_or(|found| found.is_none())
+	{
+		return Ok(None);
+	}
+
+	let file_path = file_path_option.unwrap().unwrap();
+	let model_path = match file_path.into_path() {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("Cannot Parse File Path"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Ok(None);
+		}
+	};
+
+	let load_path = model_path.clone();
+	let loaded_result = match tauri::async_runtime::spawn_blocking(move || {
+		crate::engine::storage::load_model(&load_path)
+	})
+	.await
+	{
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("Cannot Create New Process Thread"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Err(());
+		}
+	};
+
+	let loaded_model = match loaded_result {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
+			return Ok(None);
+		}
+	};
+
+	let mut guarded_state = match state.lock() {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("State Inaccessible on Load Model"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Err(());
+		}
+	};
+
+	guarded_state.loaded_model = Some(loaded_model);
+
+	Ok(model_path
+		.file_name()
+		.and_then(|name| Some(name.to_str()?.to_string())))
+}
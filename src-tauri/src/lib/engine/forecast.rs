@@ -0,0 +1,657 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use burn::{
+	backend::{ndarray::NdArrayDevice, NdArray},
+	tensor::Tensor,
+};
+use chrono::{DateTime, FixedOffset};
+use std::{borrow::Cow, collections::HashMap, path::Path, sync::Arc};
+
+use super::progress::Progress;
+use super::train::{series_embedding, validation_start};
+use super::typedef::*;
+
+const ERROR_SAVE: &'static str = "Cannot Save File";
+const ERROR_BACKTEST: &'static str = "Cannot Backtest";
+const ERROR_HORIZON: &'static str = "Forecast Horizon Too Far";
+
+/// Extrapolates beyond the end of the historical data of each series, then merges both into one
+/// graph for each predicted column. Without `horizon`, it goes as far as half of the historical
+/// data, up to 200 steps.
+pub fn predict(
+	trained_model: &TrainedModel,
+	preprocessed_data: &[HistoricalData],
+	horizon: Option<&ForecastHorizon>,
+	progress: &mut impl Progress,
+) -> Result<Forecast, ErrorInfo> {
+	let series_count = preprocessed_data.len();
+	let mut targets = Vec::<TargetForecast>::new();
+
+	for (series_index, each_series) in preprocessed_data.iter().enumerate() {
+		let past_data = normalize_past_data(trained_model, each_series);
+
+		let past_length = past_data.len();
+		let predict_length = match horizon {
+			Some(found) => found.to_steps(each_series.timestamp_interval, past_length)?,
+			None => (past_length / 2).min(200),
+		};
+
+		// The model looks back as far as it looks ahead, but never shorter than a sequence
+		let context_length = predict_length
+			.max(trained_model.sequence_size as usize)
+			.min(past_length);
+		let predict_offset = past_length - context_length;
+
+		let future_data = extrapolate(
+			trained_model,
+			&past_data[predict_offset..past_length],
+			predict_length,
+			each_series.series_id.as_ref(),
+			&mut |index| {
+				let done = series_index as f64 + (index as f64) / (predict_length as f64);
+				progress.on_predict_progress(done * 100f64 / series_count as f64)
+			},
+		);
+
+		// One graph per predicted column, each combines both historical and prediction data in
+		// (index, y0, y1) tuple
+		targets.extend(
+			trained_model
+				.predicted_columns()
+				.into_iter()
+				.enumerate()
+				.map(|(column_index, column)| {
+					let graph = (predict_offset..past_length)
+						.zip(past_data[predict_offset..past_length].iter())
+						.map(|(i, y)| (i, Some(denormalize(trained_model, column_index, y)), None))
+						.chain((past_length..).zip(future_data.iter()).map(|(i, y)| {
+							(i, None, Some(denormalize(trained_model, column_index, y)))
+						}))
+						.filter_map(|(i, y0, y1)| {
+							let x = index_to_datetime(each_series, i)?;
+							Some(ComparisonPoint { x, y0, y1 })
+						})
+						.collect::<Vec<_>>();
+
+					TargetForecast {
+						series_id: each_series.series_id.clone(),
+						column,
+						graph,
+					}
+				}),
+		);
+	}
+
+	Ok(Forecast { targets })
+}
+
+impl TrainedModel {
+	/// Columns which are forecasted, the predictable column comes first
+	pub fn predicted_columns(&self) -> Vec<Arc<String>> {
+		let mut predicted_columns = vec![self.predictable_column.clone()];
+		predicted_columns.extend(self.target_columns.iter().cloned());
+
+		predicted_columns
+	}
+}
+
+impl ForecastHorizon {
+	/// Converts the horizon into re-sampled steps, rejects the one which goes further than the
+	/// historical data goes back.
+	pub fn to_steps(
+		&self,
+		timestamp_interval: u32,
+		past_length: usize,
+	) -> Result<usize, ErrorInfo> {
+		let steps = match self {
+			ForecastHorizon::Steps { count } => *count,
+			ForecastHorizon::Duration { amount, unit } => {
+				let secs = *amount as u64 * unit.seconds() as u64;
+				secs.div_ceil(timestamp_interval.max(1) as u64) as usize
+			}
+		};
+
+		if steps == 0 {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_HORIZON),
+				message: String::from("The forecast horizon must be at least 1 step"),
+			});
+		}
+
+		if steps > past_length {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_HORIZON),
+				message: format!(
+					"Because {} steps ahead is further than the historical data, which only has {} steps",
+					steps, past_length
+				),
+			});
+		}
+
+		Ok(steps)
+	}
+}
+
+/// Hides the last `holdout` steps of each origin, forecasts them using only the data before the
+/// origin, then compares the forecast against the hidden actual values. Origins are placed back
+/// to back, the last one ends at the end of the historical data. Every series is backtested the
+/// same way, but only its predictable column is compared. The hidden steps must lie within the
+/// validation sequences, which the model never trained on.
+pub fn backtest(
+	trained_model: &TrainedModel,
+	preprocessed_data: &[HistoricalData],
+	settings: &BacktestSettings,
+	progress: &mut impl Progress,
+) -> Result<Backtest, ErrorInfo> {
+	// Same context length as the ordinary forecast, but never shorter than a sequence
+	let context_length = settings.holdout.max(trained_model.sequence_size as usize);
+	let tested_length = settings.holdout * settings.origins;
+	let total_length = tested_length * preprocessed_data.len();
+
+	if settings.holdout == 0 || settings.origins == 0 {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_BACKTEST),
+			message: String::from("Both hidden periodes and origins must be at least 1"),
+		});
+	}
+
+	let mut origins = Vec::<BacktestOrigin>::with_capacity(settings.origins);
+	let mut graphs = Vec::<TargetForecast>::with_capacity(preprocessed_data.len());
+	let mut all_actual = Vec::<f64>::with_capacity(total_length);
+	let mut all_predicted = Vec::<f64>::with_capacity(total_length);
+	let mut held_out = None::<usize>;
+
+	for (series_index, each_series) in preprocessed_data.iter().enumerate() {
+		let past_data = normalize_past_data(trained_model, each_series);
+		let past_length = past_data.len();
+
+		if tested_length + context_length > past_length {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_BACKTEST),
+				message: format!(
+					"Because only {} steps are available{}, but {} origins of {} steps need {} steps",
+					past_length,
+					each_series
+						.series_id
+						.as_ref()
+						.map(|found| format!(" in \"{}\"", found))
+						.unwrap_or_default(),
+					settings.origins,
+					settings.holdout,
+					tested_length + context_length
+				),
+			});
+		}
+
+		// Hiding the training sequences would only tell how well the model remembers them
+		let batch_count = each_series.batches.len();
+		let series_held_out = each_series
+			.batches
+			.iter()
+			.skip(validation_start(batch_count))
+			.map(|each_sequence| each_sequence.len())
+			.sum::<usize>();
+		if tested_length > series_held_out {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_BACKTEST),
+				message: format!(
+					"Because only the last {} steps{} were held out from training, but {} origins of {} steps hide {} steps",
+					series_held_out,
+					each_series
+						.series_id
+						.as_ref()
+						.map(|found| format!(" of \"{}\"", found))
+						.unwrap_or_default(),
+					settings.origins,
+					settings.holdout,
+					tested_length
+				),
+			});
+		}
+		held_out = Some(held_out.map_or(series_held_out, |found| found.min(series_held_out)));
+
+		let first_origin = past_length - tested_length;
+		let mut series_predicted = Vec::<f64>::with_capacity(tested_length);
+
+		for origin_index in 0..settings.origins {
+			let origin = first_origin + origin_index * settings.holdout;
+
+			let predicted = extrapolate(
+				trained_model,
+				&past_data[origin - context_length..origin],
+				settings.holdout,
+				each_series.series_id.as_ref(),
+				&mut |index| {
+					let done =
+						(series_index * settings.origins + origin_index) * settings.holdout + index;
+					progress.on_predict_progress((done as f64) * 100f64 / (total_length as f64))
+				},
+			)
+			.iter()
+			.map(|y| denormalize(trained_model, 0, y))
+			.collect::<Vec<_>>();
+
+			let actual = past_data[origin..origin + settings.holdout]
+				.iter()
+				.map(|y| denormalize(trained_model, 0, y))
+				.collect::<Vec<_>>();
+
+			if let Some(x) = index_to_datetime(each_series, origin) {
+				origins.push(BacktestOrigin {
+					series_id: each_series.series_id.clone(),
+					x,
+					metrics: ForecastMetrics::measure(&actual, &predicted),
+				});
+			}
+
+			all_actual.extend(actual);
+			series_predicted.extend(predicted);
+		}
+
+		// Actual values before the first origin are kept as context in the graph
+		let graph_offset = first_origin.saturating_sub(context_length);
+		let graph = (graph_offset..past_length)
+			.filter_map(|i| {
+				let x = index_to_datetime(each_series, i)?;
+				let y0 = Some(denormalize(trained_model, 0, &past_data[i]));
+				let y1 = i
+					.checked_sub(first_origin)
+					.and_then(|tested_index| series_predicted.get(tested_index).cloned());
+
+				Some(ComparisonPoint { x, y0, y1 })
+			})
+			.collect::<Vec<_>>();
+
+		graphs.push(TargetForecast {
+			series_id: each_series.series_id.clone(),
+			column: trained_model.predictable_column.clone(),
+			graph,
+		});
+		all_predicted.extend(series_predicted);
+	}
+
+	Ok(Backtest {
+		settings: settings.clone(),
+		held_out: held_out.unwrap_or_default(),
+		metrics: ForecastMetrics::measure(&all_actual, &all_predicted),
+		origins,
+		graphs,
+	})
+}
+
+/// Flattens the batches from 3D array into 2D array of [step][column], then normalizes each column
+fn normalize_past_data(
+	trained_model: &TrainedModel,
+	preprocessed_data: &HistoricalData,
+) -> Vec<Vec<f64>> {
+	let normal_param = &trained_model.normal_param;
+
+	preprocessed_data
+		.batches
+		.iter()
+		.flatten()
+		.map(|each_step| {
+			each_step
+				.iter()
+				.zip(normal_param.mean.iter().zip(normal_param.stdev.iter()))
+				.map(|(each, (mean, stdev))| (*each - mean) / stdev)
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>()
+}
+
+/// Denormalizes one column of a step
+fn denormalize(trained_model: &TrainedModel, column_index: usize, y: &[f64]) -> f64 {
+	(y[column_index] * trained_model.normal_param.stdev[column_index])
+		+ trained_model.normal_param.mean[column_index]
+}
+
+/// Timestamp of a re-sampled step, seen from the time zone of the historical data
+fn index_to_datetime(
+	preprocessed_data: &HistoricalData,
+	index: usize,
+) -> Option<DateTime<FixedOffset>> {
+	let x0 = preprocessed_data.first_timestamp as i64;
+	let x_delta = preprocessed_data.timestamp_interval as i64;
+	let x = x0 + (index as i64 * x_delta);
+
+	preprocessed_data.zone.from_timestamp(x)
+}
+
+/// Feeds the normalized `context` into the model and predicts `steps` steps after it, each
+/// predicted step is shifted into the context for the next step. Every step holds all columns, the
+/// embedding of `series_id` is appended before it goes into the model. `on_step` receives the
+/// index of each finished step.
+fn extrapolate(
+	trained_model: &TrainedModel,
+	context: &[Vec<f64>],
+	steps: usize,
+	series_id: Option<&Arc<String>>,
+	on_step: &mut impl FnMut(usize),
+) -> Vec<Vec<f64>> {
+	let device = NdArrayDevice::Cpu;
+	let context_length = context.len();
+	let input_size = trained_model.network_config.input_size;
+	let output_size = trained_model.network_config.output_size;
+	let embedding = series_embedding(&trained_model.series_ids, series_id);
+
+	// We will shift the sequence and modify its end element in this tensor
+	let flatten_context = context
+		.iter()
+		.flat_map(|each_step| each_step.iter().chain(embedding.iter()))
+		.cloned()
+		.collect::<Vec<_>>();
+	let mut tensor = Tensor::<NdArray, 1>::from_data(flatten_context.as_slice(), &device)
+		.reshape([1, context_length, input_size]);
+	let embedding_tensor = (!embedding.is_empty())
+		.then(|| Tensor::<NdArray, 1>::from_data(embedding.as_slice(), &device).unsqueeze_dim(0));
+
+	// This is the core process of prediction, matrix operation should only happen in NdArray
+	let future_tensors = (0..steps)
+		.map(|index| {
+			let (predicted, _) = trained_model.network.forward(&tensor, None);
+			let next_step = match &embedding_tensor {
+				Some(found) => Tensor::cat([predicted.clone(), found.clone()].to_vec(), 1),
+				None => predicted.clone(),
+			};
+
+			tensor = Tensor::cat(
+				[
+					tensor
+						.clone()
+						.slice([0..1, 1..context_length, 0..input_size]),
+					next_step.unsqueeze_dim(0),
+				]
+				.to_vec(),
+				1,
+			);
+
+			on_step(index);
+
+			predicted
+		})
+		.collect::<Vec<_>>();
+
+	if future_tensors.is_empty() {
+		return Vec::new();
+	}
+
+	// Gather the result after all computations are done in NdArray
+	Tensor::cat(future_tensors, 0)
+		.into_data()
+		.iter::<f64>()
+		.collect::<Vec<_>>()
+		.chunks(output_size)
+		.map(|each_step| each_step.to_vec())
+		.collect::<Vec<_>>()
+}
+
+impl TargetForecast {
+	/// Highest and lowest predicted points, in that order
+	pub fn peaks(&self) -> [Option<ComparisonPoint>; 2] {
+		let [high_peak, low_peak] = self.graph.iter().filter(|each| each.y1.is_some()).fold(
+			[Option::<&ComparisonPoint>::None; 2],
+			|[last_max, last_min], each| {
+				let y1 = each.y1.unwrap();
+				let last_max_y1 = last_max.and_then(|found| found.y1).unwrap_or(f64::MIN);
+				let last_min_y1 = last_min.and_then(|found| found.y1).unwrap_or(f64::MAX);
+
+				let max = Some(if y1 > last_max_y1 {
+					each
+				} else {
+					last_max.unwrap_or(each)
+				});
+
+				let min = Some(if y1 < last_min_y1 {
+					each
+				} else {
+					last_min.unwrap_or(each)
+				});
+
+				[max, min]
+			},
+		);
+
+		[high_peak.cloned(), low_peak.cloned()]
+	}
+}
+
+/// Saves the forecast as CSV with a "Date/Time" column, then a historical and a predicted column
+/// for each predicted column. Panel data gets a leading "Series" column, with the rows of each
+/// series one after another. Values are written with the unit of their source column, if any.
+/// Cells without value are left empty.
+pub fn write_prediction(
+	target_path: &Path,
+	forecast: &Forecast,
+	column_units: &HashMap<Arc<String>, NumberUnit>,
+) -> Result<(), ErrorInfo> {
+	let mut writer = match csv::Writer::from_path(target_path) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_SAVE),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	// Predicted columns of one series are next to each other, in the same order for every series
+	let is_panel = forecast.targets.iter().any(|each| each.series_id.is_some());
+	let first_series_id = forecast
+		.targets
+		.first()
+		.and_then(|each| each.series_id.clone());
+	let column_count = forecast
+		.targets
+		.iter()
+		.take_while(|each| each.series_id == first_series_id)
+		.count()
+		.max(1);
+
+	let mut header = Vec::<String>::new();
+	if is_panel {
+		header.push(String::from("Series"));
+	}
+	header.push(String::from("Date/Time"));
+	for each in forecast.targets.iter().take(column_count) {
+		header.push(format!("Historical {}", each.column));
+		header.push(format!("Predicted {}", each.column));
+	}
+
+	if let Err(err) = writer.write_record(&header) {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_SAVE),
+			message: err.to_string(),
+		});
+	}
+
+	// Every graph of the same series shares the same time axis
+	for each_series in forecast.targets.chunks(column_count) {
+		let row_count = each_series[0].graph.len();
+		for row_index in 0..row_count {
+			let mut record = Vec::<String>::new();
+			if is_panel {
+				record.push(
+					each_series[0]
+						.series_id
+						.as_ref()
+						.map(|found| found.to_string())
+						.unwrap_or_default(),
+				);
+			}
+			record.push(each_series[0].graph[row_index].x.to_rfc3339());
+			for each in each_series {
+				let point = &each.graph[row_index];
+				let unit = column_units.get(&each.column);
+				record.push(point.y0.map(|y| format_number(y, unit)).unwrap_or_default());
+				record.push(point.y1.map(|y| format_number(y, unit)).unwrap_or_default());
+			}
+
+			if let Err(err) = writer.write_record(&record) {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_SAVE),
+					message: err.to_string(),
+				});
+			}
+		}
+	}
+
+	if let Err(err) = writer.flush() {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed("Potentially Corrupted"),
+			message: err.to_string(),
+		});
+	}
+
+	Ok(())
+}
+
+fn format_number(value: f64, unit: Option<&NumberUnit>) -> String {
+	match unit {
+		Some(found) => found.format(value),
+		None => value.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Untrained model of a ramp, which counts up by one every step
+	fn ramp_model() -> (TrainedModel, HistoricalData) {
+		let settings = TrainSettings {
+			hidden_size: 4,
+			..Default::default()
+		};
+		let network_config = settings.network_config(1, 0);
+		let series = HistoricalData {
+			batches: (0..10)
+				.map(|sequence| {
+					(0..4)
+						.map(|step| vec![(sequence * 4 + step) as f64])
+						.collect()
+				})
+				.collect(),
+			first_timestamp: 1_700_000_000,
+			timestamp_interval: 3600,
+			..Default::default()
+		};
+
+		let trained_model = TrainedModel {
+			network: network_config.init(&NdArrayDevice::Cpu),
+			network_config,
+			normal_param: NormalParam {
+				mean: vec![0.0],
+				stdev: vec![1.0],
+			},
+			metrics: Vec::new(),
+			first_timestamp: series.first_timestamp,
+			timestamp_interval: series.timestamp_interval,
+			sequence_size: 4,
+			batch_periode: series.batch_periode,
+			datetime_column: series.datetime_column.clone(),
+			predictable_column: series.predictable_column.clone(),
+			target_columns: Vec::new(),
+			feature_columns: Vec::new(),
+			series_column: None,
+			series_ids: Vec::new(),
+		};
+
+		(trained_model, series)
+	}
+
+	#[test]
+	fn backtest_compares_hidden_steps() {
+		let (trained_model, series) = ramp_model();
+		let settings = BacktestSettings {
+			holdout: 4,
+			origins: 2,
+		};
+		let result = backtest(&trained_model, &[series.clone()], &settings, &mut ()).unwrap();
+
+		// The last 2 of 10 sequences are for validation
+		assert_eq!(result.held_out, 8);
+
+		// One sequence of context before the first origin, then the hidden steps
+		let graph = &result.graphs[0].graph;
+		assert_eq!(graph.len(), 12);
+		for (index, each) in graph.iter().enumerate() {
+			assert_eq!(Some(each.x), index_to_datetime(&series, 28 + index));
+			assert_eq!(each.y0, Some((28 + index) as f64));
+			assert_eq!(each.y1.is_some(), index >= 4);
+		}
+
+		assert_eq!(result.origins.len(), 2);
+		for (origin_index, origin) in result.origins.iter().enumerate() {
+			let hidden = &graph[4 + origin_index * 4..8 + origin_index * 4];
+			let actual = hidden.iter().filter_map(|each| each.y0).collect::<Vec<_>>();
+			let predicted = hidden.iter().filter_map(|each| each.y1).collect::<Vec<_>>();
+			let expected = ForecastMetrics::measure(&actual, &predicted);
+
+			assert_eq!(origin.x, hidden[0].x);
+			assert_eq!(origin.metrics.mae, expected.mae);
+			assert_eq!(origin.metrics.rmse, expected.rmse);
+		}
+	}
+
+	#[test]
+	fn backtest_hides_only_validation_steps() {
+		let (trained_model, series) = ramp_model();
+		let settings = BacktestSettings {
+			holdout: 4,
+			origins: 3,
+		};
+
+		assert!(backtest(&trained_model, &[series], &settings, &mut ()).is_err());
+	}
+
+	#[test]
+	fn horizon_in_steps_is_kept() {
+		let horizon = ForecastHorizon::Steps { count: 5 };
+
+		assert_eq!(horizon.to_steps(3600, 100).unwrap(), 5);
+	}
+
+	#[test]
+	fn horizon_duration_is_rounded_up_to_steps() {
+		let horizon = ForecastHorizon::Duration {
+			amount: 2,
+			unit: BatchPeriode::DAILY,
+		};
+
+		assert_eq!(horizon.to_steps(3600, 100).unwrap(), 48);
+		assert_eq!(horizon.to_steps(7 * 3600, 100).unwrap(), 7);
+	}
+
+	#[test]
+	fn horizon_out_of_range_is_rejected() {
+		assert!(ForecastHorizon::Steps { count: 0 }
+			.to_steps(3600, 100)
+			.is_err());
+		assert!(ForecastHorizon::Steps { count: 101 }
+			.to_steps(3600, 100)
+			.is_err());
+		assert!(ForecastHorizon::Duration {
+			amount: 0,
+			unit: BatchPeriode::HOURLY,
+		}
+		.to_steps(3600, 100)
+		.is_err());
+	}
+}
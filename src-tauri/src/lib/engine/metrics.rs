@@ -0,0 +1,91 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::typedef::{ColumnMetrics, ForecastMetrics};
+
+impl ForecastMetrics {
+	/// Compares each predicted value to the actual value at the same index.
+	pub fn measure(actual: &[f64], predicted: &[f64]) -> Self {
+		let count = actual.len().min(predicted.len());
+		if count == 0 {
+			return Self::default();
+		}
+
+		let pairs = actual.iter().zip(predicted.iter()).take(count);
+		let actual_mean = actual.iter().take(count).sum::<f64>() / count as f64;
+
+		let mut abs_error_sum = 0f64;
+		let mut squared_error_sum = 0f64;
+		let mut squared_total_sum = 0f64;
+		let (mut percent_error_sum, mut percent_count) = (0f64, 0usize);
+		let (mut symmetric_error_sum, mut symmetric_count) = (0f64, 0usize);
+
+		for (y, y_hat) in pairs {
+			let abs_error = (y - y_hat).abs();
+			abs_error_sum += abs_error;
+			squared_error_sum += abs_error.powi(2);
+			squared_total_sum += (y - actual_mean).powi(2);
+
+			// Percentage errors are undefined at 0, skip those points
+			if *y != 0f64 {
+				percent_error_sum += abs_error / y.abs();
+				percent_count += 1;
+			}
+
+			let denominator = y.abs() + y_hat.abs();
+			if denominator != 0f64 {
+				symmetric_error_sum += 2f64 * abs_error / denominator;
+				symmetric_count += 1;
+			}
+		}
+
+		Self {
+			mae: abs_error_sum / count as f64,
+			rmse: (squared_error_sum / count as f64).sqrt(),
+			mape: (percent_count > 0).then(|| percent_error_sum * 100f64 / percent_count as f64),
+			smape: (symmetric_count > 0)
+				.then(|| symmetric_error_sum * 100f64 / symmetric_count as f64),
+			r2: (squared_total_sum > 0f64).then(|| 1f64 - squared_error_sum / squared_total_sum),
+		}
+	}
+}
+
+impl std::fmt::Display for ForecastMetrics {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let optional = |value: Option<f64>| match value {
+			Some(found) => format!("{:.4}", found),
+			None => String::from("n/a"),
+		};
+
+		write!(
+			f,
+			"MAE {:.4}, RMSE {:.4}, MAPE {}%, sMAPE {}%, R² {}",
+			self.mae,
+			self.rmse,
+			optional(self.mape),
+			optional(self.smape),
+			optional(self.r2)
+		)
+	}
+}
+
+impl std::fmt::Display for ColumnMetrics {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}: {}", self.column, self.metrics)
+	}
+}
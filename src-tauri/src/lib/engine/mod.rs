@@ -0,0 +1,42 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Tauri-free forecasting engine, the GUI and `tarot-cli` are thin wrappers around it.
+//!
+//! The pipeline goes like this:
+//! 1. [`source::open_source_data`] then [`source::read_sheet`] to type and clean the table.
+//! 2. [`source::extract_series`] then [`preprocess::build_historical_data`] to re-sample it.
+//! 3. [`train::train`] to get a [`typedef::TrainedModel`].
+//! 4. [`forecast::predict`] to get a [`typedef::Forecast`].
+//!
+//! [`forecast::backtest`] tells how well the trained model forecasts data which it already has.
+//!
+//! A trained model can be kept with [`storage::save_model`], then [`storage::load_model`] and
+//! [`preprocess::build_historical_data_like`] skip step 3 for fresh data.
+//!
+//! Long-running steps report their progress through [`progress::Progress`].
+
+pub mod forecast;
+pub mod metrics;
+pub mod model;
+pub mod preprocess;
+pub mod progress;
+pub mod source;
+pub mod storage;
+pub mod train;
+pub mod typedef;
@@ -154,12 +154,12 @@ impl<B: Backend> LstmCell<B> {
 /// Stacked LSTM implementation supporting multiple layers
 /// Each layer processes the output of the previous layer
 #[derive(Module, Debug)]
-pub(super) struct StackedLstm<B: Backend> {
+pub struct StackedLstm<B: Backend> {
 	pub layers: Vec<LstmCell<B>>,
 }
 
 #[derive(Config, Debug)]
-pub(super) struct StackedLstmConfig {
+pub struct StackedLstmConfig {
 	pub input_size: usize,
 	pub hidden_size: usize,
 	pub num_layers: usize,
@@ -273,13 +273,13 @@ pub struct LstmNetwork<B: Backend> {
 #[derive(Config, Debug)]
 pub struct LstmNetworkConfig {
 	#[config(default = 1)]
-	pub input_size: usize, // Single feature (number sequence)
+	pub input_size: usize, // One per column, predictable column first
 	#[config(default = 128)]
 	pub hidden_size: usize, // Size of LSTM hidden state
 	#[config(default = 1)]
 	pub num_layers: usize, // Number of LSTM layers
 	#[config(default = 1)]
-	pub output_size: usize, // Predict one number
+	pub output_size: usize, // Next value of each column
 	#[config(default = 0.1)]
 	pub dropout: f64,
 	#[config(default = false)]
@@ -0,0 +1,804 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use rsl_interpolation::{Akima, InterpType, Interpolation};
+use strum::IntoEnumIterator;
+
+use super::source::resolve_zone;
+use super::typedef::*;
+
+impl BatchPeriode {
+	/// Length of one periode, a month is 30 days and a year is 365.2425 days
+	pub fn seconds(&self) -> u32 {
+		match self {
+			BatchPeriode::MINUTELY => 60u32,
+			BatchPeriode::HOURLY => 3600u32,
+			BatchPeriode::DAILY => 86400u32,
+			BatchPeriode::WEEKLY => 604800u32,
+			BatchPeriode::MONTHLY => 2592000u32,
+			BatchPeriode::YEARLY => 31556952u32,
+		}
+	}
+}
+
+impl PreprocessConfig {
+	/// Every column fed into the model, the predictable column always comes first, then the
+	/// target columns, then the feature columns. A column which is selected twice only counts once.
+	pub fn value_columns(&self) -> Vec<Arc<String>> {
+		let mut value_columns = vec![self.predictable_column.clone()];
+		for each in self
+			.target_columns
+			.iter()
+			.chain(self.feature_columns.iter())
+		{
+			if !value_columns.contains(each) {
+				value_columns.push(each.clone());
+			}
+		}
+
+		value_columns
+	}
+
+	/// Target columns and feature columns as they end up in [`Self::value_columns`]
+	pub fn split_columns(&self) -> (Vec<Arc<String>>, Vec<Arc<String>>) {
+		let mut feature_columns = self.value_columns().split_off(1);
+		let target_count = feature_columns
+			.iter()
+			.take_while(|each| self.target_columns.contains(each))
+			.count();
+		let target_columns = feature_columns.drain(..target_count).collect::<Vec<_>>();
+
+		(target_columns, feature_columns)
+	}
+}
+
+/// Lists batch periodes which are not shorter than the smallest gap between rows.
+pub fn decide_allowed_batch_periodes(
+	columns: &Vec<ColumnInfo>,
+	rows: &Vec<HashMap<Arc<String>, CellValue>>,
+) -> Vec<BatchPeriode> {
+	let fields = columns
+		.iter()
+		.filter(|each| each.column_type == ColumnType::DATETIME)
+		.map(|each| each.field.clone())
+		.collect::<Vec<_>>();
+
+	let datetime_columns = fields
+		.iter()
+		.map(|each_column| {
+			rows.iter()
+				.filter_map(|each_cell| match each_cell.get(each_column)? {
+					CellValue::DateTime(date_time) => Some(date_time),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+
+	let smallest_interval = datetime_columns
+		.iter()
+		.filter_map(|each_column| {
+			let future_iter = each_column.iter().skip(1);
+
+			each_column
+				.iter()
+				.zip(future_iter)
+				.map(|(now, next)| next.timestamp() - now.timestamp())
+				.min()
+		})
+		.min();
+
+	match smallest_interval {
+		Some(found) => {
+			let skip_count = match found.abs() as u64 {
+				0..60 => 0,           // Minutely
+				60..3600 => 1,        // Hourly
+				3600..86400 => 2,     // Daily
+				86400..604800 => 3,   // Weekly
+				604800..2592000 => 4, // Monthly
+				_ => 5,               // Yearly
+			};
+
+			BatchPeriode::iter().skip(skip_count).collect::<Vec<_>>()
+		}
+		None => BatchPeriode::iter().collect::<Vec<_>>(),
+	}
+}
+
+/// Fills the NaN values left by [`super::source::extract_series`] with the strategy of each column,
+/// after sorting each series by its timestamp. Points which still miss a value, e.g. forward fill
+/// without any earlier value, are dropped. Only the imputed columns are summarized.
+pub fn impute_missing_values(
+	selected_source_data: &mut [SourceSeries],
+	config: &PreprocessConfig,
+) -> Vec<ImputationSummary> {
+	let mut summaries = config
+		.value_columns()
+		.into_iter()
+		.map(|each_column| ImputationSummary {
+			strategy: config
+				.missing_values
+				.get(&each_column)
+				.copied()
+				.unwrap_or_default(),
+			column: each_column,
+			filled_count: 0,
+			dropped_count: 0,
+		})
+		.collect::<Vec<_>>();
+
+	for each_series in selected_source_data.iter_mut() {
+		each_series.points.sort_by_key(|each| each.0);
+
+		for (column_index, summary) in summaries.iter_mut().enumerate() {
+			let count_missing = |points: &[(u64, Vec<f64>)]| {
+				points
+					.iter()
+					.filter(|(_, values)| {
+						values.get(column_index).is_some_and(|found| found.is_nan())
+					})
+					.count() as u64
+			};
+
+			let missing_count = count_missing(&each_series.points);
+			if missing_count == 0 {
+				continue;
+			}
+
+			fill_missing_values(&mut each_series.points, column_index, summary.strategy);
+			let unfilled_count = count_missing(&each_series.points);
+			summary.filled_count += missing_count - unfilled_count;
+			summary.dropped_count += unfilled_count;
+		}
+
+		each_series
+			.points
+			.retain(|(_, values)| values.iter().all(|each| !each.is_nan()));
+	}
+
+	summaries
+		.into_iter()
+		.filter(|each| each.strategy != MissingValueStrategy::Drop)
+		.filter(|each| each.filled_count + each.dropped_count > 0)
+		.collect()
+}
+
+/// Tells user how many cells were imputed, `None` if there is nothing to tell.
+pub fn imputation_warning(summaries: &[ImputationSummary]) -> Option<ErrorInfo> {
+	if summaries.is_empty() {
+		return None;
+	}
+
+	let details = summaries
+		.iter()
+		.map(|each| {
+			format!(
+				"\"{}\" has {} cells filled by {} and {} cells dropped",
+				each.column, each.filled_count, each.strategy, each.dropped_count
+			)
+		})
+		.collect::<Vec<_>>()
+		.join(", ");
+
+	Some(ErrorInfo {
+		title: Cow::Borrowed("Missing Values Imputed"),
+		message: format!("Missing or invalid cells were imputed: {}", details),
+	})
+}
+
+/// Collapses the points sharing the same timestamp into one, because interpolation needs strictly
+/// increasing timestamps. Returns how many points were merged into another.
+pub fn aggregate_duplicate_timestamps(
+	selected_source_data: &mut [SourceSeries],
+	aggregation: DuplicateAggregation,
+) -> u64 {
+	let mut merged_count = 0u64;
+	for each_series in selected_source_data.iter_mut() {
+		// Stable sort keeps the file order of duplicates for first and last
+		each_series.points.sort_by_key(|each| each.0);
+
+		let mut output = Vec::<(u64, Vec<f64>)>::with_capacity(each_series.points.len());
+		let mut group_size = 0u64; // Points merged into the last output point so far
+		for (x, values) in std::mem::take(&mut each_series.points) {
+			let last_values = match output.last_mut() {
+				Some((last_x, last_values)) if *last_x == x => last_values,
+				_ => {
+					output.push((x, values));
+					group_size = 1;
+					continue;
+				}
+			};
+
+			group_size += 1;
+			merged_count += 1;
+			for (last, value) in last_values.iter_mut().zip(values) {
+				*last = match aggregation {
+					DuplicateAggregation::Mean => *last + (value - *last) / group_size as f64,
+					DuplicateAggregation::Sum => *last + value,
+					DuplicateAggregation::First => *last,
+					DuplicateAggregation::Last => value,
+					DuplicateAggregation::Min => last.min(value),
+					DuplicateAggregation::Max => last.max(value),
+				};
+			}
+		}
+
+		each_series.points = output;
+	}
+
+	merged_count
+}
+
+/// Tells user how many duplicate timestamps were merged, `None` if there were none.
+pub fn aggregation_warning(
+	merged_count: u64,
+	aggregation: DuplicateAggregation,
+) -> Option<ErrorInfo> {
+	if merged_count == 0 {
+		return None;
+	}
+
+	Some(ErrorInfo {
+		title: Cow::Borrowed("Duplicate Timestamps Merged"),
+		message: format!(
+			"There are {} rows sharing their timestamp with another row, merged into one by their {}",
+			merged_count, aggregation
+		),
+	})
+}
+
+/// Fills NaN values of one column in a sorted series, the ones which cannot be filled stay NaN.
+fn fill_missing_values(
+	points: &mut [(u64, Vec<f64>)],
+	column_index: usize,
+	strategy: MissingValueStrategy,
+) {
+	match strategy {
+		MissingValueStrategy::Drop => (),
+		MissingValueStrategy::ForwardFill => carry_forward(
+			points
+				.iter_mut()
+				.map(|(_, values)| &mut values[column_index]),
+		),
+		MissingValueStrategy::BackwardFill => carry_forward(
+			points
+				.iter_mut()
+				.rev()
+				.map(|(_, values)| &mut values[column_index]),
+		),
+		MissingValueStrategy::Constant(constant) => points
+			.iter_mut()
+			.map(|(_, values)| &mut values[column_index])
+			.filter(|each| each.is_nan())
+			.for_each(|each| *each = constant),
+		MissingValueStrategy::Mean => {
+			let known_values = points
+				.iter()
+				.map(|(_, values)| values[column_index])
+				.filter(|each| !each.is_nan())
+				.collect::<Vec<_>>();
+			if known_values.is_empty() {
+				return;
+			}
+
+			let mean = known_values.iter().sum::<f64>() / known_values.len() as f64;
+			points
+				.iter_mut()
+				.map(|(_, values)| &mut values[column_index])
+				.filter(|each| each.is_nan())
+				.for_each(|each| *each = mean);
+		}
+		MissingValueStrategy::Linear => {
+			// Nearest known point after each point, found by walking backward
+			let mut next_known = vec![None; points.len()];
+			let mut next = Option::<(u64, f64)>::None;
+			for (index, (x, values)) in points.iter().enumerate().rev() {
+				if !values[column_index].is_nan() {
+					next = Some((*x, values[column_index]));
+				}
+				next_known[index] = next;
+			}
+
+			let mut previous = Option::<(u64, f64)>::None;
+			for ((x, values), next) in points.iter_mut().zip(next_known) {
+				let value = &mut values[column_index];
+				if !value.is_nan() {
+					previous = Some((*x, *value));
+					continue;
+				}
+
+				if let (Some((x0, y0)), Some((x1, y1))) = (previous, next) {
+					*value = match x1 > x0 {
+						true => y0 + (y1 - y0) * (*x - x0) as f64 / (x1 - x0) as f64,
+						false => y0,
+					};
+				}
+			}
+		}
+	}
+}
+
+/// Replaces each NaN with the last known value before it, in the order of `cells`.
+fn carry_forward<'a>(cells: impl Iterator<Item = &'a mut f64>) {
+	let mut last_known = Option::<f64>::None;
+	for each in cells {
+		match (each.is_nan(), last_known) {
+			(true, Some(found)) => *each = found,
+			(true, None) => (),
+			(false, _) => last_known = Some(*each),
+		}
+	}
+}
+
+/// Sorts each series by its timestamp, then re-samples each column into equally spaced batches.
+/// All series share the sequence size of the densest one, so a single model fits them all. Series
+/// which are too short to have a pattern are skipped.
+pub fn build_historical_data(
+	mut selected_source_data: Vec<SourceSeries>,
+	config: &PreprocessConfig,
+) -> Result<Vec<HistoricalData>, ErrorInfo> {
+	selected_source_data
+		.iter_mut()
+		.for_each(|each| each.points.sort_unstable_by_key(|each| each.0));
+
+	let zone = resolve_zone(&config.datetime_format)?;
+	let batch_periode = config.batch_periode;
+	let sequence_size = selected_source_data
+		.iter()
+		.map(|each| calculate_batch_info(&each.points, batch_periode, zone).sequence_size)
+		.max()
+		.unwrap_or(1);
+
+	let output = resample_each_series(
+		selected_source_data,
+		config,
+		zone,
+		batch_periode,
+		sequence_size,
+		batch_periode.seconds() / sequence_size,
+	)?;
+
+	if output.is_empty() {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed("Unable to Spot The Pattern"),
+			message: format!(
+				"Because data pattern is not there {}, try to change the quicker period",
+				batch_periode
+			),
+		});
+	}
+
+	Ok(output)
+}
+
+/// Same as [`build_historical_data`], but re-samples with interval and sequence size of a loaded
+/// model, so it can forecast fresh data without training. The target and feature columns must be
+/// as many as the model was trained with.
+pub fn build_historical_data_like(
+	mut selected_source_data: Vec<SourceSeries>,
+	config: &PreprocessConfig,
+	trained_model: &TrainedModel,
+) -> Result<Vec<HistoricalData>, ErrorInfo> {
+	let (target_columns, feature_columns) = config.split_columns();
+	if target_columns.len() != trained_model.target_columns.len() {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed("Target Columns Mismatch"),
+			message: format!(
+				"This model was trained to predict {} other columns ({}), but {} were selected",
+				trained_model.target_columns.len(),
+				join_columns(&trained_model.target_columns),
+				target_columns.len()
+			),
+		});
+	}
+
+	if feature_columns.len() != trained_model.feature_columns.len() {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed("Feature Columns Mismatch"),
+			message: format!(
+				"This model was trained with {} feature columns ({}), but {} were selected",
+				trained_model.feature_columns.len(),
+				join_columns(&trained_model.feature_columns),
+				feature_columns.len()
+			),
+		});
+	}
+
+	selected_source_data
+		.iter_mut()
+		.for_each(|each| each.points.sort_unstable_by_key(|each| each.0));
+
+	let output = resample_each_series(
+		selected_source_data,
+		config,
+		resolve_zone(&config.datetime_format)?,
+		trained_model.batch_periode,
+		trained_model.sequence_size,
+		trained_model.timestamp_interval,
+	)?;
+
+	if output.is_empty() {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed("Unable to Spot The Pattern"),
+			message: format!(
+				"The data is too short for this model, it needs at least 2 {} periodes",
+				trained_model.batch_periode
+			),
+		});
+	}
+
+	Ok(output)
+}
+
+/// Re-samples every sorted series with the same sequence size and interval, skipping the ones
+/// which have less than 2 sequences.
+fn resample_each_series(
+	selected_source_data: Vec<SourceSeries>,
+	config: &PreprocessConfig,
+	zone: Zone,
+	batch_periode: BatchPeriode,
+	sequence_size: u32,
+	interval: u32,
+) -> Result<Vec<HistoricalData>, ErrorInfo> {
+	let (target_columns, feature_columns) = config.split_columns();
+	let sequence_secs = interval as u64 * sequence_size as u64;
+
+	let mut output = Vec::<HistoricalData>::with_capacity(selected_source_data.len());
+	for each_series in selected_source_data {
+		let first_timestamp = each_series
+			.points
+			.first()
+			.map(|each| each.0)
+			.unwrap_or_default();
+		let last_timestamp = each_series
+			.points
+			.last()
+			.map(|each| each.0)
+			.unwrap_or_default();
+
+		let batch_info = BatchInfo {
+			sequence_size,
+			sequence_count: ((last_timestamp - first_timestamp) / sequence_secs.max(1)) as u32,
+			interval,
+		};
+
+		if batch_info.sequence_count < 2 {
+			continue;
+		}
+
+		let batches = resample_or_fail(&each_series.points, &batch_info)?;
+
+		output.push(HistoricalData {
+			series_id: each_series.id,
+			batches,
+			first_timestamp,
+			timestamp_interval: interval,
+			batch_periode,
+			datetime_column: config.datetime_column.clone(),
+			predictable_column: config.predictable_column.clone(),
+			target_columns: target_columns.clone(),
+			feature_columns: feature_columns.clone(),
+			series_column: config.series_column.clone(),
+			zone,
+		});
+	}
+
+	Ok(output)
+}
+
+fn join_columns(columns: &[Arc<String>]) -> String {
+	if columns.is_empty() {
+		return String::from("none");
+	}
+
+	columns
+		.iter()
+		.map(|each| format!("\"{}\"", each))
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+fn resample_or_fail(
+	selected_source_data: &[(u64, Vec<f64>)],
+	batch_info: &BatchInfo,
+) -> Result<Vec<Vec<Vec<f64>>>, ErrorInfo> {
+	match resample_to_batches(selected_source_data, batch_info) {
+		Ok(ok) => Ok(ok),
+		Err(err) => Err(ErrorInfo {
+			title: Cow::Borrowed("Re-sampling Failed"),
+			message: err,
+		}),
+	}
+}
+
+/// Boundaries of each periode follow the wall clock of `zone`, e.g. a day starts at its midnight.
+pub fn calculate_batch_info(
+	selected_source_data: &[(u64, Vec<f64>)],
+	periode: BatchPeriode,
+	zone: Zone,
+) -> BatchInfo {
+	let timeseries_data = selected_source_data
+		.iter()
+		.filter_map(|(x, _)| Some(zone.from_timestamp(*x as i64)?.naive_local()))
+		.collect::<Vec<_>>();
+
+	// Find for sequence size
+	let mut sequence_size = 0u32;
+	let mut count = 0u32;
+	let mut last_datetime = timeseries_data
+		.get(0)
+		.and_then(|found| Some(*found))
+		.unwrap_or(NaiveDateTime::MIN);
+	match periode {
+		BatchPeriode::MINUTELY => timeseries_data.iter().for_each(|each| {
+			if each.minute() != last_datetime.minute() {
+				sequence_size = sequence_size.max(count);
+				count = 0;
+				last_datetime = *each;
+			} else {
+				count += 1;
+			}
+		}),
+		BatchPeriode::HOURLY => timeseries_data.iter().for_each(|each| {
+			if each.hour() != last_datetime.hour() {
+				sequence_size = sequence_size.max(count);
+				count = 0;
+				last_datetime = *each;
+			} else {
+				count += 1;
+			}
+		}),
+		BatchPeriode::DAILY => timeseries_data.iter().for_each(|each| {
+			if each.day() != last_datetime.day() {
+				sequence_size = sequence_size.max(count);
+				count = 0;
+				last_datetime = *each;
+			} else {
+				count += 1;
+			}
+		}),
+		BatchPeriode::WEEKLY => timeseries_data.iter().for_each(|each| {
+			if each.iso_week().week() != last_datetime.iso_week().week() {
+				sequence_size = sequence_size.max(count);
+				count = 0;
+				last_datetime = *each;
+			} else {
+				count += 1;
+			}
+		}),
+		BatchPeriode::MONTHLY => timeseries_data.iter().for_each(|each| {
+			if each.month() != last_datetime.month() {
+				sequence_size = sequence_size.max(count);
+				count = 0;
+				last_datetime = *each;
+			} else {
+				count += 1;
+			}
+		}),
+		BatchPeriode::YEARLY => timeseries_data.iter().for_each(|each| {
+			if each.year() != last_datetime.year() {
+				sequence_size = sequence_size.max(count);
+				count = 0;
+				last_datetime = *each;
+			} else {
+				count += 1;
+			}
+		}),
+	}
+
+	if sequence_size == 0 {
+		sequence_size = 1;
+	}
+
+	let interval = periode.seconds() / sequence_size;
+
+	let min_datetime = timeseries_data
+		.first()
+		.and_then(|found| Some(*found))
+		.unwrap_or_default();
+	let max_datetime = timeseries_data
+		.last()
+		.and_then(|found| Some(*found))
+		.unwrap_or(min_datetime);
+	let delta_secs = (max_datetime - min_datetime).num_seconds().abs() as u64;
+
+	let sequence_count = (delta_secs / (interval as u64 * sequence_size as u64)) as u32;
+
+	BatchInfo {
+		sequence_size,
+		sequence_count,
+		interval,
+	}
+}
+
+/// Re-samples every column separately, then zips them back into [sequence][step][column].
+pub fn resample_to_batches(
+	selected_source_data: &[(u64, Vec<f64>)],
+	batch_info: &BatchInfo,
+) -> Result<Vec<Vec<Vec<f64>>>, String> {
+	if selected_source_data.is_empty() {
+		return Err(format!("Cannot re-sample an empty table"));
+	}
+
+	let xa = selected_source_data
+		.iter()
+		.map(|each| each.0 as f64)
+		.collect::<Vec<_>>();
+
+	let xa_first = xa.first().cloned().unwrap_or_default();
+	let xa_last = xa.last().cloned().unwrap_or(xa_first);
+
+	let column_count = selected_source_data[0].1.len();
+	let step_count = batch_info.sequence_count as usize * batch_info.sequence_size as usize;
+
+	let mut output =
+		vec![
+			vec![Vec::<f64>::with_capacity(column_count); batch_info.sequence_size as usize];
+			batch_info.sequence_count as usize
+		];
+	for column_index in 0..column_count {
+		let ya = selected_source_data
+			.iter()
+			.map(|each| each.1[column_index])
+			.collect::<Vec<_>>();
+
+		let interp = match Akima.build(&xa, &ya) {
+			Ok(ok) => ok,
+			Err(err) => return Err(err.to_string()),
+		};
+
+		let mut interp_cache = rsl_interpolation::Accelerator::new();
+
+		let ya_first = ya.first().cloned().unwrap_or_default();
+		let ya_last = ya.last().cloned().unwrap_or(ya_first);
+
+		for step_index in 0..step_count {
+			let x = xa_first + (batch_info.interval as f64 * step_index as f64);
+
+			// GSL's Akima cannot do extrapolation, need to clip it
+			let y = if x <= xa_first {
+				ya_first
+			} else if x >= xa_last {
+				ya_last
+			} else {
+				match interp.eval(&xa, &ya, x, &mut interp_cache) {
+					Ok(ok) => ok,
+					Err(err) => return Err(err.to_string()),
+				}
+			};
+
+			let sequence_size = batch_info.sequence_size as usize;
+			output[step_index / sequence_size][step_index % sequence_size].push(y);
+		}
+	}
+
+	Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn config_with(strategy: MissingValueStrategy) -> PreprocessConfig {
+		let price = Arc::new(String::from("price"));
+		PreprocessConfig {
+			predictable_column: price.clone(),
+			missing_values: HashMap::from([(price, strategy)]),
+			..Default::default()
+		}
+	}
+
+	fn series_of(points: &[(u64, f64)]) -> Vec<SourceSeries> {
+		vec![SourceSeries {
+			id: None,
+			points: points.iter().map(|(x, y)| (*x, vec![*y])).collect(),
+		}]
+	}
+
+	fn values_of(series: &[SourceSeries]) -> Vec<(u64, f64)> {
+		series[0]
+			.points
+			.iter()
+			.map(|(x, values)| (*x, values[0]))
+			.collect()
+	}
+
+	#[test]
+	fn missing_values_are_interpolated_by_timestamp() {
+		let mut series = series_of(&[(30, 4.0), (0, 1.0), (10, f64::NAN), (20, f64::NAN)]);
+		let summaries =
+			impute_missing_values(&mut series, &config_with(MissingValueStrategy::Linear));
+
+		assert_eq!(
+			values_of(&series),
+			vec![(0, 1.0), (10, 2.0), (20, 3.0), (30, 4.0)]
+		);
+		assert_eq!(summaries.len(), 1);
+		assert_eq!(summaries[0].filled_count, 2);
+		assert_eq!(summaries[0].dropped_count, 0);
+	}
+
+	#[test]
+	fn missing_values_without_neighbor_are_dropped() {
+		let mut series = series_of(&[(0, f64::NAN), (10, 2.0), (20, f64::NAN)]);
+		let summaries =
+			impute_missing_values(&mut series, &config_with(MissingValueStrategy::ForwardFill));
+
+		assert_eq!(values_of(&series), vec![(10, 2.0), (20, 2.0)]);
+		assert_eq!(summaries[0].filled_count, 1);
+		assert_eq!(summaries[0].dropped_count, 1);
+	}
+
+	#[test]
+	fn missing_values_are_dropped_by_default() {
+		let mut series = series_of(&[(0, 1.0), (10, f64::NAN), (20, 3.0)]);
+		let summaries = impute_missing_values(&mut series, &config_with(Default::default()));
+
+		assert_eq!(values_of(&series), vec![(0, 1.0), (20, 3.0)]);
+		assert!(summaries.is_empty());
+	}
+
+	#[test]
+	fn duplicate_timestamps_are_aggregated() {
+		let points = [(10, 5.0), (0, 1.0), (0, 3.0), (0, 2.0)];
+		let aggregated = |aggregation| {
+			let mut series = series_of(&points);
+			let merged_count = aggregate_duplicate_timestamps(&mut series, aggregation);
+			(merged_count, values_of(&series))
+		};
+
+		assert_eq!(
+			aggregated(DuplicateAggregation::Mean),
+			(2, vec![(0, 2.0), (10, 5.0)])
+		);
+		assert_eq!(
+			aggregated(DuplicateAggregation::Sum),
+			(2, vec![(0, 6.0), (10, 5.0)])
+		);
+		assert_eq!(
+			aggregated(DuplicateAggregation::First),
+			(2, vec![(0, 1.0), (10, 5.0)])
+		);
+		assert_eq!(
+			aggregated(DuplicateAggregation::Last),
+			(2, vec![(0, 2.0), (10, 5.0)])
+		);
+		assert_eq!(
+			aggregated(DuplicateAggregation::Min),
+			(2, vec![(0, 1.0), (10, 5.0)])
+		);
+		assert_eq!(
+			aggregated(DuplicateAggregation::Max),
+			(2, vec![(0, 3.0), (10, 5.0)])
+		);
+	}
+
+	#[test]
+	fn unique_timestamps_are_kept() {
+		let mut series = series_of(&[(0, 1.0), (10, 2.0)]);
+
+		assert_eq!(
+			aggregate_duplicate_timestamps(&mut series, DuplicateAggregation::Mean),
+			0
+		);
+		assert_eq!(values_of(&series), vec![(0, 1.0), (10, 2.0)]);
+	}
+}
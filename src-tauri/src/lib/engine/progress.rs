@@ -0,0 +1,36 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::typedef::{IterativePoint, StopReason};
+
+/// Receives progress of the long-running steps. Every method does nothing by default, so
+/// implement only the needed ones.
+pub trait Progress {
+	/// Called after each training epoch, `point.x` is the epoch together with the validation metrics
+	/// of that epoch
+	fn on_train_progress(&mut self, _point: IterativePoint) {}
+
+	/// Called once training has stopped, the returned model is the one from `best_epoch`
+	fn on_train_stop(&mut self, _reason: StopReason, _best_epoch: u32) {}
+
+	/// Called after each predicted step, in percent
+	fn on_predict_progress(&mut self, _percent: f64) {}
+}
+
+/// Ignores any progress
+impl Progress for () {}
@@ -0,0 +1,2614 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::{
+	borrow::Cow,
+	collections::HashMap,
+	error::Error,
+	fs::File,
+	hash::RandomState,
+	io::{BufReader, Cursor},
+	path::Path,
+	sync::Arc,
+};
+
+use arrow::{
+	array::{Array, ArrayRef, AsArray},
+	compute::cast,
+	datatypes::{DataType as ArrowType, Float64Type, TimeUnit, TimestampMillisecondType},
+	ipc::reader::{FileReader, StreamReader},
+	util::display::array_value_to_string,
+};
+use calamine::{DataType, Reader};
+use chrono::{
+	format::{Item, StrftimeItems},
+	DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+};
+use chrono_tz::Tz;
+use csv::{Position, ReaderBuilder};
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parse_datetime::parse_datetime;
+use rusqlite::{types::ValueRef, Connection, OpenFlags};
+use serde_json::{Map, Value};
+
+use super::typedef::*;
+
+const ERROR_EXTENSION: &'static str = "File Type Unsupported";
+const ERROR_HEADER: &'static str = "Cannot Read Header";
+const ERROR_CONTENT: &'static str = "Failed to Read Content";
+const ERROR_INCOMPLETE: &'static str = "Data is Incomplete";
+const ERROR_INCOMPLETE_SUFFIX: &'static str = "this file";
+const ERROR_INCONSISTENT: &'static str = "Inconsistent Data Type";
+const ERROR_MODIFIED: &'static str = "Selected Data just Modified";
+const ERROR_RESET: &'static str = "Cannot Re-read the Data";
+const ERROR_DIALECT: &'static str = "CSV Format Unsupported";
+const ERROR_DATETIME_FORMAT: &'static str = "Date/Time Format Unsupported";
+const ERROR_PRE_EPOCH: &'static str = "Date/Time is Too Early";
+
+const SNIFF_LINE_COUNT: usize = 20;
+const CURRENCY_SYMBOLS: [&'static str; 10] = ["US$", "R$", "$", "€", "£", "¥", "₹", "₩", "₽", "₺"];
+const MAGNITUDE_SUFFIXES: [(char, f64); 4] = [('K', 1e3), ('M', 1e6), ('B', 1e9), ('T', 1e12)];
+const DELIMITER_CANDIDATES: [char; 4] = [',', ';', '\t', '|'];
+const TIMESTAMP_NAME_HINTS: [&'static str; 4] = ["time", "date", "epoch", "created"];
+const TIMESTAMP_RANGES: [(NumericTimestamp, f64, f64); 4] = [
+	(NumericTimestamp::Seconds, 1e8, 1e10), // Between 1973 and 2286
+	(NumericTimestamp::Milliseconds, 1e11, 1e13),
+	(NumericTimestamp::Microseconds, 1e14, 1e16),
+	(NumericTimestamp::ExcelSerial, 1e4, 1e5), // Between 1927 and 2173
+];
+const OFFENDING_SAMPLE_COUNT: usize = 5;
+const NAIVE_DATETIME_FORMATS: [&'static str; 7] = [
+	"%Y-%m-%d %H:%M:%S%.f",
+	"%Y-%m-%dT%H:%M:%S%.f",
+	"%Y-%m-%d %H:%M",
+	"%Y-%m-%dT%H:%M",
+	"%Y/%m/%d %H:%M:%S%.f",
+	"%Y/%m/%d %H:%M",
+	"%Y-%m-%d %I:%M:%S %p",
+];
+const NAIVE_DATE_FORMATS: [&'static str; 5] =
+	["%Y-%m-%d", "%Y/%m/%d", "%d %b %Y", "%b %d %Y", "%b %d, %Y"];
+const EXCEL_UNIX_EPOCH_DAYS: f64 = 25569.0; // 1970-01-01 in Excel serial days
+
+/// Opens CSV, spreadsheet, Parquet, Arrow IPC, JSON, or SQLite file based on its extension. Only
+/// CSV cares about `csv_dialect`.
+pub fn open_source_data(
+	source_path: &Path,
+	csv_dialect: &CsvDialectOverride,
+) -> Result<SourceData, ErrorInfo> {
+	let extension = match source_path.extension() {
+		Some(found) => match found.to_str() {
+			Some(found) => found.to_lowercase(),
+			None => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_EXTENSION),
+					message: String::from("Cannot transform file extension encoding"),
+				})
+			}
+		},
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_EXTENSION),
+				message: format!(
+					"Because \"{}\" file has no extension",
+					source_path.to_string_lossy().into_owned()
+				),
+			})
+		}
+	};
+
+	match extension.as_str() {
+		"csv" | "tsv" | "txt" => match std::fs::read(source_path) {
+			Ok(bytes) => Ok(SourceData::Csv(open_csv(&bytes, csv_dialect)?)),
+			Err(err) => Err(ErrorInfo {
+				title: Cow::Borrowed("Failed to Read CSV File"),
+				message: err.to_string(),
+			}),
+		},
+		"xlsx" | "xls" | "xlsb" | "ods" => match calamine::open_workbook_auto(source_path) {
+			Ok(sheet_file) => Ok(SourceData::Spreadsheet(sheet_file)),
+			Err(err) => Err(ErrorInfo {
+				title: Cow::Owned(format!("Failed to Read {} File", extension.to_uppercase())),
+				message: err.to_string(),
+			}),
+		},
+		"parquet" | "pq" => match read_parquet(source_path) {
+			Ok(table) => Ok(SourceData::Parquet(table)),
+			Err(err) => Err(ErrorInfo {
+				title: Cow::Borrowed("Failed to Read Parquet File"),
+				message: err.to_string(),
+			}),
+		},
+		"arrow" | "arrows" | "feather" | "ipc" => match read_arrow_ipc(source_path) {
+			Ok(table) => Ok(SourceData::Arrow(table)),
+			Err(err) => Err(ErrorInfo {
+				title: Cow::Borrowed("Failed to Read Arrow File"),
+				message: err.to_string(),
+			}),
+		},
+		"json" | "ndjson" | "jsonl" => match read_json(source_path) {
+			Ok(table) => Ok(SourceData::Json(table)),
+			Err(err) => Err(ErrorInfo {
+				title: Cow::Borrowed("Failed to Read JSON File"),
+				message: err.to_string(),
+			}),
+		},
+		"sqlite" | "sqlite3" | "db" | "db3" => {
+			match Connection::open_with_flags(source_path, OpenFlags::SQLITE_OPEN_READ_ONLY) {
+				Ok(connection) => Ok(SourceData::Sqlite(connection)),
+				Err(err) => Err(ErrorInfo {
+					title: Cow::Borrowed("Failed to Open SQLite Database"),
+					message: err.to_string(),
+				}),
+			}
+		}
+		_ => Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_EXTENSION),
+			message: format!("Cannot parse \"{}\" file extension", extension),
+		}),
+	}
+}
+
+fn open_csv(bytes: &[u8], csv_dialect: &CsvDialectOverride) -> Result<CsvSource, ErrorInfo> {
+	let (content, dialect) = sniff_csv_dialect(bytes, csv_dialect)?;
+	let reader = ReaderBuilder::new()
+		.delimiter(dialect.delimiter as u8)
+		.quote(dialect.quote as u8)
+		.has_headers(dialect.has_header)
+		.from_reader(Cursor::new(content.into_bytes()));
+
+	Ok(CsvSource { reader, dialect })
+}
+
+/// Decodes the whole CSV file into UTF-8, then guesses every field of the dialect which is not
+/// chosen by user from the first few lines.
+pub fn sniff_csv_dialect(
+	bytes: &[u8],
+	csv_dialect: &CsvDialectOverride,
+) -> Result<(String, CsvDialect), ErrorInfo> {
+	let encoding = match &csv_dialect.encoding {
+		Some(label) => match Encoding::for_label(label.as_bytes()) {
+			Some(found) => found,
+			None => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_DIALECT),
+					message: format!("Cannot recognize \"{}\" encoding", label),
+				})
+			}
+		},
+		None => match Encoding::for_bom(bytes) {
+			Some((found, _)) => found,
+			None if std::str::from_utf8(bytes).is_ok() => UTF_8,
+			None => WINDOWS_1252, // Superset of Latin-1, the usual non-UTF-8 export
+		},
+	};
+
+	let (content, _, _) = encoding.decode(bytes);
+	let sample_lines = content
+		.lines()
+		.filter(|each| !each.trim().is_empty())
+		.take(SNIFF_LINE_COUNT)
+		.collect::<Vec<_>>();
+
+	let delimiter = csv_dialect
+		.delimiter
+		.unwrap_or_else(|| sniff_delimiter(&sample_lines));
+	let quote = csv_dialect
+		.quote
+		.unwrap_or_else(|| sniff_quote(&sample_lines, delimiter));
+	if let Some(found) = [delimiter, quote].iter().find(|each| !each.is_ascii()) {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_DIALECT),
+			message: format!("Expected a single ASCII character, got \"{}\"", found),
+		});
+	}
+
+	let sample_records = ReaderBuilder::new()
+		.delimiter(delimiter as u8)
+		.quote(quote as u8)
+		.has_headers(false)
+		.flexible(true)
+		.from_reader(sample_lines.join("\n").as_bytes())
+		.records()
+		.filter_map(|each| each.ok())
+		.map(|each| each.iter().map(|cell| cell.to_string()).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	let decimal_comma = csv_dialect
+		.decimal_comma
+		.unwrap_or_else(|| delimiter != ',' && sniff_decimal_comma(&sample_records));
+	let has_header = csv_dialect
+		.has_header
+		.unwrap_or_else(|| sniff_header(&sample_records, decimal_comma));
+
+	let dialect = CsvDialect {
+		delimiter,
+		quote,
+		has_header,
+		encoding: encoding.name().to_lowercase(),
+		decimal_comma,
+	};
+
+	Ok((content.into_owned(), dialect))
+}
+
+/// The delimiter appears the same number of times on every line, the more the merrier.
+fn sniff_delimiter(sample_lines: &[&str]) -> char {
+	DELIMITER_CANDIDATES
+		.iter()
+		.filter_map(|candidate| {
+			let counts = sample_lines
+				.iter()
+				.map(|line| count_unquoted(line, *candidate))
+				.collect::<Vec<_>>();
+			let min_count = *counts.iter().min()?;
+			if min_count == 0 {
+				return None;
+			}
+
+			let is_consistent = counts.iter().all(|each| *each == min_count);
+			Some((is_consistent, min_count, *candidate))
+		})
+		.max_by_key(|(is_consistent, min_count, _)| (*is_consistent, *min_count))
+		.map(|(_, _, candidate)| candidate)
+		.unwrap_or(',')
+}
+
+fn count_unquoted(line: &str, target: char) -> usize {
+	let mut is_quoted = false;
+	line.chars()
+		.filter(|each| {
+			if *each == '"' {
+				is_quoted = !is_quoted;
+			}
+			!is_quoted && *each == target
+		})
+		.count()
+}
+
+fn sniff_quote(sample_lines: &[&str], delimiter: char) -> char {
+	let count_opening = |quote: char| {
+		sample_lines
+			.iter()
+			.flat_map(|line| line.split(delimiter))
+			.filter(|cell| cell.trim_start().starts_with(quote))
+			.count()
+	};
+
+	match count_opening('\'') > count_opening('"') {
+		true => '\'',
+		false => '"',
+	}
+}
+
+fn sniff_decimal_comma(sample_records: &[Vec<String>]) -> bool {
+	let cells = sample_records.iter().flatten();
+	let comma_count = cells
+		.clone()
+		.filter(|each| each.contains(',') && parse_number(each, true).is_some())
+		.count();
+	let dot_count = cells
+		.filter(|each| each.contains('.') && parse_number(each, false).is_some())
+		.count();
+
+	comma_count > dot_count
+}
+
+/// Header row has neither number nor date/time, otherwise it is just the first data row.
+fn sniff_header(sample_records: &[Vec<String>], decimal_comma: bool) -> bool {
+	let cell_format = CellFormat {
+		decimal_comma,
+		..Default::default()
+	};
+
+	match sample_records.first() {
+		Some(first_row) => first_row
+			.iter()
+			.all(|each| matches!(parse_cell(each, &cell_format), CellValue::String(_))),
+		None => true,
+	}
+}
+
+fn read_parquet(source_path: &Path) -> Result<ArrowTable, Box<dyn Error>> {
+	let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(source_path)?)?;
+	let schema = builder.schema().clone();
+	let batches = builder.build()?.collect::<Result<Vec<_>, _>>()?;
+	Ok(ArrowTable { schema, batches })
+}
+
+fn read_arrow_ipc(source_path: &Path) -> Result<ArrowTable, Box<dyn Error>> {
+	// Feather v2 is the random access format, fallback to the streaming one if it is not
+	if let Ok(reader) = FileReader::try_new(File::open(source_path)?, None) {
+		let schema = reader.schema();
+		let batches = reader.collect::<Result<Vec<_>, _>>()?;
+		return Ok(ArrowTable { schema, batches });
+	}
+
+	let reader = StreamReader::try_new(BufReader::new(File::open(source_path)?), None)?;
+	let schema = reader.schema();
+	let batches = reader.collect::<Result<Vec<_>, _>>()?;
+	Ok(ArrowTable { schema, batches })
+}
+
+fn read_json(source_path: &Path) -> Result<JsonTable, Box<dyn Error>> {
+	let content = std::fs::read_to_string(source_path)?;
+
+	// Whole file is an array of objects, otherwise each line is an object
+	let values = match serde_json::from_str::<Value>(&content) {
+		Ok(Value::Array(found)) => found,
+		Ok(_) => return Err("Expected an array of objects at the top level".into()),
+		Err(_) => content
+			.lines()
+			.filter(|each| !each.trim().is_empty())
+			.map(serde_json::from_str::<Value>)
+			.collect::<Result<Vec<_>, _>>()?,
+	};
+
+	let mut column_lookup = HashMap::<String, usize>::new();
+	let mut column_names = Vec::<String>::new();
+	let mut flattened_rows = Vec::<Vec<(usize, Value)>>::with_capacity(values.len());
+
+	for (row_index, each_value) in values.iter().enumerate() {
+		let object = match each_value {
+			Value::Object(found) => found,
+			_ => return Err(format!("Row {} is not a JSON object", row_index + 1).into()),
+		};
+
+		let mut pairs = Vec::<(String, Value)>::new();
+		flatten_json_object(None, object, &mut pairs);
+
+		// Columns are kept in the order they first appear
+		let flattened = pairs
+			.into_iter()
+			.map(|(path, value)| {
+				let column_index = *column_lookup.entry(path.clone()).or_insert_with(|| {
+					column_names.push(path);
+					column_names.len() - 1
+				});
+				(column_index, value)
+			})
+			.collect::<Vec<_>>();
+		flattened_rows.push(flattened);
+	}
+
+	// Missing keys become empty cells like in CSV, which `missing_values` may impute
+	let rows = flattened_rows
+		.into_iter()
+		.map(|each_row| {
+			let mut values = vec![Value::Null; column_names.len()];
+			for (column_index, value) in each_row {
+				values[column_index] = value;
+			}
+			values
+		})
+		.collect::<Vec<_>>();
+
+	Ok(JsonTable { column_names, rows })
+}
+
+fn flatten_json_object(
+	prefix: Option<&str>,
+	object: &Map<String, Value>,
+	output: &mut Vec<(String, Value)>,
+) {
+	for (key, value) in object {
+		let path = match prefix {
+			Some(found) => format!("{}.{}", found, key),
+			None => key.clone(),
+		};
+
+		match value {
+			Value::Object(found) => flatten_json_object(Some(&path), found, output),
+			_ => output.push((path, value.clone())),
+		}
+	}
+}
+
+/// Parses every flattened value with the format of its column.
+fn read_json_rows(
+	table: &JsonTable,
+	cell_formats: &[CellFormat],
+	unit_counter: &mut UnitCounter,
+) -> Vec<Vec<CellValue>> {
+	table
+		.rows
+		.iter()
+		.map(|each_row| {
+			each_row
+				.iter()
+				.zip(cell_formats)
+				.enumerate()
+				.map(|(index, (value, cell_format))| {
+					let (cell, unit) = parse_json_cell(value, cell_format);
+					unit_counter.count(index, unit);
+					cell
+				})
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>()
+}
+
+/// Missing key is null, which is an empty cell like in CSV.
+fn parse_json_cell(value: &Value, cell_format: &CellFormat) -> (CellValue, Option<NumberUnit>) {
+	match value {
+		Value::Null => (CellValue::default(), None),
+		Value::Bool(found) => (CellValue::Boolean(*found), None),
+		Value::Number(found) => match found.as_f64() {
+			Some(number) => (number_cell(number, cell_format), None),
+			None => (CellValue::String(found.to_string()), None),
+		},
+		Value::String(found) => parse_cell_with_unit(found, cell_format),
+		Value::Array(_) | Value::Object(_) => (CellValue::String(value.to_string()), None),
+	}
+}
+
+/// Worksheet (or database table) names, or `None` for the other file types which have only one
+/// sheet.
+pub fn list_tabs(source_data: &SourceData) -> Result<Option<Vec<String>>, ErrorInfo> {
+	match source_data {
+		SourceData::Spreadsheet(sheets) => Ok(Some(sheets.sheet_names())),
+		SourceData::Sqlite(connection) => match list_sqlite_tables(connection) {
+			Ok(names) => Ok(Some(names)),
+			Err(err) => Err(ErrorInfo {
+				title: Cow::Borrowed("Cannot List Database Tables"),
+				message: err.to_string(),
+			}),
+		},
+		_ => Ok(None),
+	}
+}
+
+fn list_sqlite_tables(connection: &Connection) -> rusqlite::Result<Vec<String>> {
+	let mut statement = connection.prepare(
+		"SELECT name FROM sqlite_master WHERE type IN ('table', 'view') \
+		AND name NOT LIKE 'sqlite_%' ORDER BY name",
+	)?;
+	let names = statement.query_map([], |row| row.get::<_, String>(0))?;
+	names.collect()
+}
+
+/// Runs the query of a database "tab", which is either a table name or a custom `SELECT` query.
+fn read_sqlite_rows(
+	connection: &Connection,
+	tab_name: &str,
+	datetime_format: &DatetimeFormat,
+	zone: Zone,
+) -> rusqlite::Result<TypedTable> {
+	let is_table = list_sqlite_tables(connection)?
+		.iter()
+		.any(|each| each == tab_name);
+	let query = match is_table {
+		true => format!("SELECT * FROM \"{}\"", tab_name.replace('"', "\"\"")),
+		false => tab_name.to_string(),
+	};
+
+	let mut statement = connection.prepare(&query)?;
+	let column_names = statement
+		.column_names()
+		.iter()
+		.map(|each| each.to_string())
+		.collect::<Vec<_>>();
+	let column_count = column_names.len();
+	let cell_formats = column_cell_formats(&column_names, datetime_format, zone, false);
+
+	let mut unit_counter = UnitCounter::default();
+	let rows = statement
+		.query_map([], |row| {
+			(0..column_count)
+				.map(|index| {
+					let (cell, unit) = parse_sqlite_cell(row.get_ref(index)?, &cell_formats[index]);
+					unit_counter.count(index, unit);
+					Ok(cell)
+				})
+				.collect::<rusqlite::Result<Vec<_>>>()
+		})?
+		.collect::<rusqlite::Result<Vec<_>>>()?;
+
+	Ok(TypedTable {
+		column_names,
+		rows,
+		units: unit_counter.into_units(column_count),
+	})
+}
+
+/// SQLite has no date/time type, so only text cells are parsed further.
+fn parse_sqlite_cell(cell: ValueRef, cell_format: &CellFormat) -> (CellValue, Option<NumberUnit>) {
+	match cell {
+		ValueRef::Null => (CellValue::default(), None),
+		ValueRef::Integer(found) => (number_cell(found as f64, cell_format), None),
+		ValueRef::Real(found) => (number_cell(found, cell_format), None),
+		ValueRef::Text(found) => parse_cell_with_unit(&String::from_utf8_lossy(found), cell_format),
+		ValueRef::Blob(found) => (
+			CellValue::String(String::from_utf8_lossy(found).into_owned()),
+			None,
+		),
+	}
+}
+
+/// Reads the whole sheet (or the other file, or database query), decides the type of each column,
+/// then drops rows that do not match with the decided types. Date/time cells are read with
+/// `datetime_format`, and `type_overrides` replaces the decided type of some columns. Worksheet
+/// header and data rows are found with `sheet_layout`. Rows are kept if their only mismatching
+/// cells are in number columns which `missing_values` imputes.
+pub fn read_sheet(
+	source_data: &mut SourceData,
+	tab_name: &str,
+	datetime_format: &DatetimeFormat,
+	type_overrides: &HashMap<Arc<String>, ColumnType>,
+	sheet_layout: &SheetLayout,
+	missing_values: &HashMap<Arc<String>, MissingValueStrategy>,
+) -> Result<SheetContent, ErrorInfo> {
+	let zone = resolve_zone(datetime_format)?;
+
+	// CSV will always ignore this
+	let selected_sheet = match source_data {
+		SourceData::Spreadsheet(sheets) => match sheets.worksheet_range(tab_name) {
+			Ok(ok) => Some(ok),
+			Err(err) => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed("Cannot Read Worksheet"),
+					message: err.to_string(),
+				})
+			}
+		},
+		_ => None,
+	};
+
+	// Same for the query of database
+	let mut selected_table = match source_data {
+		SourceData::Sqlite(connection) => {
+			match read_sqlite_rows(connection, tab_name, datetime_format, zone) {
+				Ok(ok) => Some(ok),
+				Err(err) => {
+					return Err(ErrorInfo {
+						title: Cow::Borrowed("Cannot Query Database"),
+						message: err.to_string(),
+					})
+				}
+			}
+		}
+		_ => None,
+	};
+
+	let column_names = match source_data {
+		SourceData::None => unreachable!(),
+		SourceData::Csv(source) => match source.reader.headers() {
+			Ok(ok) if source.dialect.has_header => {
+				ok.iter().map(|each| each.to_string()).collect::<Vec<_>>()
+			}
+			Ok(ok) => (1..=ok.len())
+				.map(|index| format!("Column {}", index))
+				.collect::<Vec<_>>(),
+			Err(err) => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_HEADER),
+					message: err.to_string(),
+				})
+			}
+		},
+		SourceData::Spreadsheet(_) => {
+			match combine_sheet_headers(selected_sheet.as_ref().unwrap(), sheet_layout) {
+				Some(found) => found,
+				None => {
+					return Err(ErrorInfo {
+						title: Cow::Borrowed(ERROR_HEADER),
+						message: format!(
+							"The selected sheet has no header at row {}",
+							sheet_layout.header_row + 1
+						),
+					})
+				}
+			}
+		}
+		SourceData::Parquet(table) | SourceData::Arrow(table) => table
+			.schema
+			.fields()
+			.iter()
+			.map(|each| each.name().clone())
+			.collect::<Vec<_>>(),
+		SourceData::Json(table) => table.column_names.clone(),
+		SourceData::Sqlite(_) => selected_table.as_ref().unwrap().column_names.clone(),
+	};
+
+	let decimal_comma = match source_data {
+		SourceData::Csv(source) => source.dialect.decimal_comma,
+		_ => false,
+	};
+	let cell_formats = column_cell_formats(&column_names, datetime_format, zone, decimal_comma);
+
+	// Units are only found in cells written as text
+	let mut unit_counter = UnitCounter::default();
+	let mut cells = match source_data {
+		SourceData::None => unreachable!(),
+		SourceData::Csv(source) => {
+			// Re-reading the same file starts from its end otherwise, e.g. with another format
+			if let Err(err) = source.reader.seek(Position::new()) {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_RESET),
+					message: err.to_string(),
+				});
+			}
+
+			// Seeking back makes the header row readable as a record again
+			let header_count = source.dialect.has_header as usize;
+			let results = source
+				.reader
+				.records()
+				.skip(header_count)
+				.map(|each| {
+					let row = each.map_err(|err| err.to_string())?;
+					Ok(row
+						.iter()
+						.zip(&cell_formats)
+						.enumerate()
+						.map(|(index, (cell, cell_format))| {
+							let (cell, unit) = parse_cell_with_unit(cell, cell_format);
+							unit_counter.count(index, unit);
+							cell
+						})
+						.collect::<Vec<_>>())
+				})
+				.collect::<Vec<Result<Vec<CellValue>, String>>>();
+
+			let error = results.iter().find_map(|each| each.as_ref().err()).cloned();
+			if let Some(message) = error {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_CONTENT),
+					message,
+				});
+			}
+
+			results
+				.into_iter()
+				.filter_map(|each| each.ok())
+				.collect::<Vec<_>>()
+		}
+		SourceData::Spreadsheet(_) => {
+			let sheet = selected_sheet.as_ref().unwrap();
+			sheet
+				.rows()
+				.skip(sheet_layout.data_start()) // Skip preamble and header rows
+				.map(|each| {
+					each.iter()
+						.zip(&cell_formats)
+						.enumerate()
+						.map(|(index, (cell, cell_format))| {
+							let (cell, unit) = parse_spreadsheet_cell(cell, cell_format);
+							unit_counter.count(index, unit);
+							cell
+						})
+						.collect::<Vec<_>>()
+				})
+				.collect::<Vec<_>>()
+		}
+		SourceData::Parquet(table) | SourceData::Arrow(table) => {
+			read_arrow_rows(table, &cell_formats)
+		}
+		SourceData::Json(table) => read_json_rows(table, &cell_formats, &mut unit_counter),
+		SourceData::Sqlite(_) => std::mem::take(&mut selected_table.as_mut().unwrap().rows),
+	};
+
+	let column_count = column_names.len();
+	let column_units = match source_data {
+		SourceData::Sqlite(_) => selected_table.take().unwrap().units,
+		_ => unit_counter.into_units(column_count),
+	};
+
+	// Count how many data types (string, number, ...) occurence each column
+	let column_type_counters = cells.iter().fold(
+		std::iter::repeat_n(ColumnCounter::default(), column_count).collect::<Vec<_>>(),
+		|mut counters, each_row| {
+			for i in 0..column_count {
+				let counter = counters.get_mut(i).unwrap();
+				let each_cell = match each_row.get(i) {
+					Some(found) => found,
+					None => continue,
+				};
+
+				match each_cell {
+					CellValue::String(_) => counter.string += 1,
+					CellValue::Number(_) => counter.number += 1,
+					CellValue::RowID(_) => {
+						unreachable!()
+					}
+					CellValue::DateTime(_) => counter.datetime += 1,
+					CellValue::Boolean(_) => counter.boolean += 1,
+				}
+			}
+
+			counters
+		},
+	);
+
+	// Pick the most occuring data type for each column
+	let column_types = column_type_counters
+		.iter()
+		.map(|each| {
+			let mut winner = ColumnType::STRING;
+			if each.string < each.number {
+				winner = ColumnType::NUMBER
+			}
+			if each.number < each.datetime {
+				winner = ColumnType::DATETIME
+			}
+			if each.datetime < each.boolean {
+				winner = ColumnType::BOOLEAN
+			}
+			winner
+		})
+		.collect::<Vec<_>>();
+
+	// Columnar files already know their types, only text columns need the vote above
+	let mut column_types = match source_data {
+		SourceData::Parquet(table) | SourceData::Arrow(table) => table
+			.schema
+			.fields()
+			.iter()
+			.zip(column_types)
+			.zip(&cell_formats)
+			.map(
+				|((field, voted), cell_format)| match cell_format.numeric_timestamp {
+					Some(_) => voted,
+					None => arrow_column_type(field.data_type()).unwrap_or(voted),
+				},
+			)
+			.collect::<Vec<_>>(),
+		_ => column_types,
+	};
+
+	// User knows better than the majority, text column takes every cell as its text
+	let is_overridden = column_names
+		.iter()
+		.map(|name| type_overrides.contains_key(&name.to_lowercase()))
+		.collect::<Vec<_>>();
+	for (index, name) in column_names.iter().enumerate() {
+		let overridden_type = match type_overrides.get(&name.to_lowercase()) {
+			Some(found) => *found,
+			None => continue,
+		};
+
+		column_types[index] = overridden_type;
+		if overridden_type == ColumnType::STRING {
+			for each_cell in cells
+				.iter_mut()
+				.filter_map(|each_row| each_row.get_mut(index))
+			{
+				*each_cell = CellValue::String(cell_to_text(each_cell));
+			}
+		}
+	}
+
+	// Machine logs often count time as number, which is only guessed if nothing else is date/time
+	let mut datetime_format = datetime_format.clone();
+	let is_datetime_chosen = datetime_format.format.is_some() || datetime_format.numeric.is_some();
+	if !is_datetime_chosen && !column_types.contains(&ColumnType::DATETIME) {
+		if let Some((index, numeric)) =
+			detect_numeric_timestamp(&column_names, &column_types, &cells)
+				.filter(|(index, _)| !is_overridden[*index])
+		{
+			let cell_format = CellFormat {
+				numeric_timestamp: Some(numeric),
+				..cell_formats[index].clone()
+			};
+			for each_row in cells.iter_mut() {
+				let value = match each_row.get(index) {
+					Some(CellValue::Number(found)) => *found,
+					_ => continue,
+				};
+				each_row[index] = number_cell(value, &cell_format);
+			}
+
+			column_types[index] = ColumnType::DATETIME;
+			datetime_format.column = Some(Arc::new(column_names[index].to_lowercase()));
+			datetime_format.numeric = Some(numeric);
+		}
+	}
+
+	let columns = column_names
+		.iter()
+		.zip(&column_types)
+		.zip(column_units)
+		.map(|((name, column_type), unit)| ColumnInfo {
+			field: Arc::new(name.to_lowercase()),
+			header_name: name.clone(),
+			column_type: *column_type,
+			unit: unit.filter(|_| *column_type == ColumnType::NUMBER),
+		})
+		.collect::<Vec<_>>();
+
+	let source_name = match (&selected_sheet, &*source_data) {
+		(Some(_), _) | (None, SourceData::Sqlite(_)) => tab_name,
+		_ => ERROR_INCOMPLETE_SUFFIX,
+	};
+
+	// Chosen format must fit the column it was chosen for, otherwise guessing would be no better
+	let is_datetime_chosen = datetime_format.format.is_some() || datetime_format.numeric.is_some();
+	let formatted_column = columns
+		.iter()
+		.find(|each| is_datetime_chosen && datetime_format.column.as_ref() == Some(&each.field));
+	if let Some(found) = formatted_column.filter(|each| each.column_type != ColumnType::DATETIME) {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_DATETIME_FORMAT),
+			message: match &datetime_format.format {
+				Some(pattern) => format!(
+					"Most cells of \"{}\" column do not match \"{}\" format",
+					found.header_name, pattern
+				),
+				None => format!(
+					"Most cells of \"{}\" column are not numeric timestamps",
+					found.header_name
+				),
+			},
+		});
+	}
+
+	let selected_datetime_column = match formatted_column.or(columns
+		.iter()
+		.find(|each| each.column_type == ColumnType::DATETIME))
+	{
+		Some(found) => found.field.clone(),
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_INCOMPLETE),
+				message: format!("There is no Date/Time column in {}", source_name),
+			})
+		}
+	};
+
+	let selected_predictable_column = match columns
+		.iter()
+		.find(|each| each.column_type == ColumnType::NUMBER)
+	{
+		Some(found) => found.field.clone(),
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_INCOMPLETE),
+				message: format!("There is no predictable column in {}", source_name),
+			})
+		}
+	};
+
+	let id_field = Arc::new(String::from("id"));
+
+	let mut column_inferences = columns
+		.iter()
+		.zip(&column_type_counters)
+		.zip(&is_overridden)
+		.map(|((each_column, counts), is_overridden)| ColumnInference {
+			field: each_column.field.clone(),
+			counts: *counts,
+			is_overridden: *is_overridden,
+			is_imputed: each_column.column_type == ColumnType::NUMBER
+				&& missing_values
+					.get(&each_column.field)
+					.is_some_and(|found| *found != MissingValueStrategy::Drop),
+			..Default::default()
+		})
+		.collect::<Vec<_>>();
+
+	let row_count = cells.len();
+	let mut dropped_row_indices = Vec::<u64>::new();
+	let rows = cells
+		.iter()
+		.zip(0..row_count)
+		.filter(|(each_row, row_index)| {
+			// Every cell is checked, so the report tells all the columns which drop this row
+			let mut is_row_ok = true;
+			for (i, inference) in column_inferences.iter_mut().enumerate() {
+				let each_cell = each_row.get(i);
+				let actual = match each_cell {
+					Some(CellValue::String(_)) => Some(ColumnType::STRING),
+					Some(CellValue::Number(_)) => Some(ColumnType::NUMBER),
+					Some(CellValue::DateTime(_)) => Some(ColumnType::DATETIME),
+					Some(CellValue::Boolean(_)) => Some(ColumnType::BOOLEAN),
+					Some(CellValue::RowID(_)) | None => None,
+				};
+
+				if actual == column_types.get(i).cloned() {
+					continue;
+				}
+
+				is_row_ok &= inference.is_imputed;
+				inference.offending_count += 1;
+				if inference.offending_samples.len() < OFFENDING_SAMPLE_COUNT {
+					inference.offending_samples.push(OffendingCell {
+						row_id: *row_index as u64,
+						value: each_cell.map(cell_to_text).unwrap_or_default(),
+					});
+				}
+			}
+
+			if !is_row_ok {
+				dropped_row_indices.push(*row_index as u64);
+			}
+
+			is_row_ok
+		})
+		.map(|(each_row, id)| {
+			let pairs_iter = columns
+				.iter()
+				.zip(each_row)
+				.map(|(each_column, each_cell)| (each_column.field.clone(), each_cell.clone()));
+
+			let pairs_iter_with_id = [(id_field.clone(), CellValue::RowID(id as u32))]
+				.into_iter()
+				.chain(pairs_iter);
+
+			HashMap::<_, _, RandomState>::from_iter(pairs_iter_with_id)
+		})
+		.collect::<Vec<_>>();
+
+	let filtered_row_count = rows.len();
+	let source_row_count = cells.len();
+	let mut warning = None;
+	if filtered_row_count != source_row_count {
+		if filtered_row_count < 10 {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_INCONSISTENT),
+				message: format!(
+					"Only {} rows are unusable after data cleaning, left only {} rows which is too few",
+					source_row_count - filtered_row_count,
+					filtered_row_count
+				),
+			});
+		} else {
+			warning = Some(ErrorInfo {
+				title: Cow::Borrowed(ERROR_INCONSISTENT),
+				message: format!(
+					"Data cleaning was automatically done and there are {} rows dropped because of inconsistent cell data type",
+					source_row_count - filtered_row_count
+				),
+			});
+		}
+	}
+
+	if filtered_row_count < 10 {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed("Not Enough Rows"),
+			message: format!(
+				"You selected only {} rows, we need more than (or equal) 10 rows",
+				filtered_row_count
+			),
+		});
+	}
+
+	// We don't want to re-parse the header row
+	let column_lookup = HashMap::from_iter(
+		columns
+			.iter()
+			.map(|each| each.field.clone())
+			.zip(0..column_count),
+	);
+
+	Ok(SheetContent {
+		columns,
+		rows,
+		selected_datetime_column,
+		selected_predictable_column,
+		column_lookup,
+		dropped_row_indices,
+		warning,
+		datetime_format,
+		column_inferences,
+	})
+}
+
+/// Reads (timestamp, value) pairs of the chosen columns, skipping both dropped and unselected rows.
+pub fn extract_series(
+	source_data: &mut SourceData,
+	column_lookup: &HashMap<Arc<String>, usize>,
+	dropped_row_indices: &[u64],
+	config: &PreprocessConfig,
+) -> Result<Vec<SourceSeries>, ErrorInfo> {
+	let datetime_index = match column_lookup.get(&config.datetime_column) {
+		Some(found) => *found,
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_MODIFIED),
+				message: String::from("Date/time column suddenly gone in this file/table"),
+			})
+		}
+	};
+
+	// Predictable column comes first, then the feature columns
+	let mut value_indices = Vec::<usize>::new();
+	for each_column in config.value_columns() {
+		match column_lookup.get(&each_column) {
+			Some(found) => value_indices.push(*found),
+			None => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_MODIFIED),
+					message: format!(
+						"\"{}\" column suddenly gone in this file/table",
+						each_column
+					),
+				})
+			}
+		}
+	}
+
+	// Missing cells of these columns are kept as NaN instead of skipping their rows
+	let imputed_values = config
+		.value_columns()
+		.iter()
+		.map(|each_column| {
+			config
+				.missing_values
+				.get(each_column)
+				.is_some_and(|found| *found != MissingValueStrategy::Drop)
+		})
+		.collect::<Vec<_>>();
+
+	let series_index = match &config.series_column {
+		Some(series_column) => match column_lookup.get(series_column) {
+			Some(found) => Some(*found),
+			None => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_MODIFIED),
+					message: format!(
+						"\"{}\" column suddenly gone in this file/table",
+						series_column
+					),
+				})
+			}
+		},
+		None => None,
+	};
+
+	// Pattern or numeric unit only applies to its own column, which is usually the selected one
+	let datetime_format = &config.datetime_format;
+	let zone = resolve_zone(datetime_format)?;
+	let mut column_names = vec![String::new(); column_lookup.len()];
+	for (field, index) in column_lookup {
+		if let Some(found) = column_names.get_mut(*index) {
+			*found = field.to_string();
+		}
+	}
+	let decimal_comma = match source_data {
+		SourceData::Csv(source) => source.dialect.decimal_comma,
+		_ => false,
+	};
+	let cell_formats = column_cell_formats(&column_names, datetime_format, zone, decimal_comma);
+	let datetime_cell_format = cell_formats
+		.get(datetime_index)
+		.cloned()
+		.unwrap_or_default();
+
+	let rows = match source_data {
+		SourceData::None => unreachable!(),
+		SourceData::Csv(source) => {
+			if let Err(err) = source.reader.seek(Position::new()) {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_RESET),
+					message: err.to_string(),
+				});
+			}
+
+			// Seeking back makes the header row readable as a record again
+			let header_count = source.dialect.has_header as usize;
+			let mut row_index = 0u64;
+			source
+				.reader
+				.records()
+				.skip(header_count)
+				.filter(|_| {
+					let is_id_listed = config.row_selection.ids.contains(&row_index);
+					let is_dropped = dropped_row_indices.contains(&row_index);
+					row_index += 1;
+
+					!is_dropped
+						&& match config.row_selection.selection_type {
+							SelectionType::EXCLUDE => !is_id_listed,
+							SelectionType::INCLUDE => is_id_listed,
+						}
+				})
+				.filter_map(|each| {
+					let each_row = each.ok()?;
+
+					let x = match parse_cell(each_row.get(datetime_index)?, &datetime_cell_format) {
+						CellValue::DateTime(found) => found.timestamp(),
+						_ => return None,
+					};
+
+					// Skip the whole row if any of its values is not a number, unless it is imputed
+					let y = value_indices
+						.iter()
+						.zip(&imputed_values)
+						.map(|(index, is_imputed)| {
+							let value = each_row
+								.get(*index)
+								.and_then(|cell| parse_number(cell, decimal_comma));
+							impute_or_skip(value, *is_imputed)
+						})
+						.collect::<Option<Vec<_>>>()?;
+
+					let series_id = match series_index {
+						Some(index) => Some(each_row.get(index)?.trim().to_string()),
+						None => None,
+					};
+
+					Some((series_id, x, y))
+				})
+				.collect::<Vec<_>>()
+		}
+		SourceData::Spreadsheet(sheets) => {
+			let tab_name = match &config.tab_name {
+				Some(found) => found,
+				None => {
+					return Err(ErrorInfo {
+						title: Cow::Borrowed(ERROR_INCOMPLETE),
+						message: String::from(
+							"Because tab name is missing while loading spreadsheet",
+						),
+					})
+				}
+			};
+
+			let sheet = match sheets.worksheet_range(tab_name) {
+				Ok(ok) => ok,
+				Err(err) => {
+					return Err(ErrorInfo {
+						title: Cow::Borrowed(ERROR_MODIFIED),
+						message: err.to_string(),
+					})
+				}
+			};
+
+			let mut row_index = 0u64;
+			sheet
+				.rows()
+				.skip(config.sheet_layout.data_start())
+				.filter(|_| {
+					let is_id_listed = config.row_selection.ids.contains(&row_index);
+					let is_dropped = dropped_row_indices.contains(&row_index);
+					row_index += 1;
+
+					!is_dropped
+						&& match config.row_selection.selection_type {
+							SelectionType::EXCLUDE => !is_id_listed,
+							SelectionType::INCLUDE => is_id_listed,
+						}
+				})
+				.filter_map(|each_row| {
+					let datetime_cell = each_row.get(datetime_index)?;
+					let x = match parse_spreadsheet_cell(datetime_cell, &datetime_cell_format).0 {
+						CellValue::DateTime(found) => found.timestamp(),
+						_ => return None,
+					};
+
+					// Numbers written as text with a unit still count
+					let y = value_indices
+						.iter()
+						.zip(&imputed_values)
+						.map(|(index, is_imputed)| {
+							let value = each_row.get(*index).and_then(|cell| {
+								cell.as_f64()
+									.or_else(|| parse_number(&cell.to_string(), false))
+							});
+							impute_or_skip(value, *is_imputed)
+						})
+						.collect::<Option<Vec<_>>>()?;
+
+					let series_id = match series_index {
+						Some(index) => Some(each_row.get(index)?.to_string().trim().to_string()),
+						None => None,
+					};
+
+					Some((series_id, x, y))
+				})
+				.collect::<Vec<_>>()
+		}
+		SourceData::Parquet(table) | SourceData::Arrow(table) => filter_typed_rows(
+			read_arrow_rows(table, &cell_formats),
+			datetime_index,
+			&value_indices,
+			&imputed_values,
+			series_index,
+			dropped_row_indices,
+			config,
+		),
+		SourceData::Json(table) => filter_typed_rows(
+			read_json_rows(table, &cell_formats, &mut UnitCounter::default()),
+			datetime_index,
+			&value_indices,
+			&imputed_values,
+			series_index,
+			dropped_row_indices,
+			config,
+		),
+		SourceData::Sqlite(connection) => {
+			let tab_name = match &config.tab_name {
+				Some(found) => found,
+				None => {
+					return Err(ErrorInfo {
+						title: Cow::Borrowed(ERROR_INCOMPLETE),
+						message: String::from(
+							"Because table name is missing while loading database",
+						),
+					})
+				}
+			};
+
+			let table = match read_sqlite_rows(connection, tab_name, datetime_format, zone) {
+				Ok(ok) => ok,
+				Err(err) => {
+					return Err(ErrorInfo {
+						title: Cow::Borrowed(ERROR_MODIFIED),
+						message: err.to_string(),
+					})
+				}
+			};
+
+			filter_typed_rows(
+				table.rows,
+				datetime_index,
+				&value_indices,
+				&imputed_values,
+				series_index,
+				dropped_row_indices,
+				config,
+			)
+		}
+	};
+
+	group_series(rows)
+}
+
+/// Same as the CSV and spreadsheet path of [`extract_series`], but the cells are already typed.
+fn filter_typed_rows(
+	cells: Vec<Vec<CellValue>>,
+	datetime_index: usize,
+	value_indices: &[usize],
+	imputed_values: &[bool],
+	series_index: Option<usize>,
+	dropped_row_indices: &[u64],
+	config: &PreprocessConfig,
+) -> Vec<(Option<String>, i64, Vec<f64>)> {
+	let mut row_index = 0u64;
+	cells
+		.into_iter()
+		.filter(|_| {
+			let is_id_listed = config.row_selection.ids.contains(&row_index);
+			let is_dropped = dropped_row_indices.contains(&row_index);
+			row_index += 1;
+
+			!is_dropped
+				&& match config.row_selection.selection_type {
+					SelectionType::EXCLUDE => !is_id_listed,
+					SelectionType::INCLUDE => is_id_listed,
+				}
+		})
+		.filter_map(|each_row| {
+			let x = match each_row.get(datetime_index)? {
+				CellValue::DateTime(found) => found.timestamp(),
+				_ => return None,
+			};
+
+			let y = value_indices
+				.iter()
+				.zip(imputed_values)
+				.map(|(index, is_imputed)| {
+					let value = match each_row.get(*index) {
+						Some(CellValue::Number(found)) => Some(*found),
+						_ => None,
+					};
+					impute_or_skip(value, *is_imputed)
+				})
+				.collect::<Option<Vec<_>>>()?;
+
+			let series_id = match series_index {
+				Some(index) => Some(cell_to_text(each_row.get(index)?)),
+				None => None,
+			};
+
+			Some((series_id, x, y))
+		})
+		.collect::<Vec<_>>()
+}
+
+impl UnitCounter {
+	fn count(&mut self, column_index: usize, unit: Option<NumberUnit>) {
+		if self.counts.len() <= column_index {
+			self.counts.resize_with(column_index + 1, HashMap::new);
+		}
+		*self.counts[column_index].entry(unit).or_insert(0) += 1;
+	}
+
+	fn into_units(self, column_count: usize) -> Vec<Option<NumberUnit>> {
+		let mut units = self
+			.counts
+			.into_iter()
+			.map(|each| {
+				each.into_iter()
+					.max_by_key(|(_, count)| *count)
+					.and_then(|(unit, _)| unit)
+			})
+			.collect::<Vec<_>>();
+		units.resize(column_count, None);
+		units
+	}
+}
+
+/// Missing value of an imputed column becomes NaN, otherwise `None` skips its row.
+fn impute_or_skip(value: Option<f64>, is_imputed: bool) -> Option<f64> {
+	match (value, is_imputed) {
+		(Some(found), _) => Some(found),
+		(None, true) => Some(f64::NAN),
+		(None, false) => None,
+	}
+}
+
+/// Groups rows by their series id, series are kept in the order they first appear.
+/// Rejects timestamps before the Unix epoch, re-sampling counts the seconds since then.
+fn group_series(
+	rows: Vec<(Option<String>, i64, Vec<f64>)>,
+) -> Result<Vec<SourceSeries>, ErrorInfo> {
+	let mut series_lookup = HashMap::<Option<String>, usize>::new();
+	let mut output = Vec::<SourceSeries>::new();
+
+	for (series_id, x, y) in rows {
+		let x = match u64::try_from(x) {
+			Ok(ok) => ok,
+			Err(_) => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(ERROR_PRE_EPOCH),
+					message: format!(
+						"Because {} is before 1970-01-01, which is not supported",
+						DateTime::from_timestamp(x, 0)
+							.map(|found| found.to_rfc3339())
+							.unwrap_or_else(|| x.to_string())
+					),
+				})
+			}
+		};
+
+		let series_index = *series_lookup.entry(series_id.clone()).or_insert_with(|| {
+			output.push(SourceSeries {
+				id: series_id.map(Arc::new),
+				points: Vec::new(),
+			});
+			output.len() - 1
+		});
+
+		output[series_index].points.push((x, y));
+	}
+
+	Ok(output)
+}
+
+pub fn parse_cell(cell: &str, cell_format: &CellFormat) -> CellValue {
+	parse_cell_with_unit(cell, cell_format).0
+}
+
+/// Same as [`parse_cell`], but also tells the unit written around the number, if any.
+pub fn parse_cell_with_unit(
+	cell: &str,
+	cell_format: &CellFormat,
+) -> (CellValue, Option<NumberUnit>) {
+	// Numbers of the date/time column are counted from an epoch
+	if cell_format.numeric_timestamp.is_some() {
+		if let Some(value) = parse_number(cell, cell_format.decimal_comma) {
+			return (number_cell(value, cell_format), None);
+		}
+	}
+
+	// Cells of the date/time column must follow the chosen pattern, no guessing
+	if cell_format.datetime_pattern.is_some() {
+		return match parse_datetime_text(cell, cell_format) {
+			Some(found) => (CellValue::DateTime(found), None),
+			None => (CellValue::String(cell.to_string()), None),
+		};
+	}
+
+	if let Some(datetime_value) = parse_datetime_text(cell, cell_format) {
+		(CellValue::DateTime(datetime_value), None)
+	} else if let Some((number_value, unit)) =
+		parse_number_with_unit(cell, cell_format.decimal_comma)
+	{
+		(number_cell(number_value, cell_format), unit)
+	} else if let Ok(boolean_value) = cell.parse::<bool>() {
+		(CellValue::Boolean(boolean_value), None)
+	} else {
+		(CellValue::String(cell.to_string()), None)
+	}
+}
+
+/// Parses date/time text with the pattern of `cell_format` if any, otherwise guesses its format.
+/// Text without offset is the wall clock of the chosen time zone.
+fn parse_datetime_text(cell: &str, cell_format: &CellFormat) -> Option<DateTime<FixedOffset>> {
+	let text = cell.trim();
+	let zone = cell_format.zone;
+	if let Some(pattern) = &cell_format.datetime_pattern {
+		if let Ok(found) = DateTime::parse_from_str(text, pattern) {
+			return Some(zone.convert(&found));
+		}
+		if let Ok(found) = NaiveDateTime::parse_from_str(text, pattern) {
+			return zone.localize(&found);
+		}
+		let found = NaiveDate::parse_from_str(text, pattern).ok()?;
+		return zone.localize(&found.and_time(NaiveTime::MIN));
+	}
+
+	// Wall clock without offset belongs to the chosen time zone
+	let naive_datetime = NAIVE_DATETIME_FORMATS
+		.iter()
+		.find_map(|each| NaiveDateTime::parse_from_str(text, each).ok());
+	if let Some(found) = naive_datetime {
+		return zone.localize(&found);
+	}
+	let naive_date = NAIVE_DATE_FORMATS
+		.iter()
+		.find_map(|each| NaiveDate::parse_from_str(text, each).ok());
+	if let Some(found) = naive_date {
+		return zone.localize(&found.and_time(NaiveTime::MIN));
+	}
+
+	// The guessing parser reads text without offset in local time zone, so it gets only the rest
+	if !has_explicit_offset(text) {
+		return None;
+	}
+	let found = DateTime::parse_from_rfc3339(text)
+		.ok()
+		.or_else(|| parse_datetime(text).ok())?;
+	Some(zone.convert(&found))
+}
+
+/// Whether the text ends with `Z`, `UTC`, `GMT`, or a numeric offset after its time of day such
+/// as `+07:00`.
+fn has_explicit_offset(text: &str) -> bool {
+	let lowercase = text.to_ascii_lowercase();
+	if ["utc", "gmt"].iter().any(|each| lowercase.ends_with(each)) {
+		return true;
+	}
+	if let Some(found) = lowercase.strip_suffix('z') {
+		return found.ends_with(|each: char| each.is_ascii_digit());
+	}
+
+	match lowercase.rfind(['+', '-']) {
+		Some(index) => {
+			let digits = lowercase[index + 1..].replace(':', "");
+			lowercase[..index].contains(':')
+				&& (1..=4).contains(&digits.len())
+				&& digits.chars().all(|each| each.is_ascii_digit())
+		}
+		None => false,
+	}
+}
+
+/// Parses `1234.56`, or `1.234,56` if the decimal separator is a comma. See
+/// [`parse_number_with_unit`] for the other accepted formats.
+pub fn parse_number(cell: &str, decimal_comma: bool) -> Option<f64> {
+	parse_number_with_unit(cell, decimal_comma).map(|(value, _)| value)
+}
+
+/// Besides plain numbers, accepts currency symbols (`$123.45`, `12,50 €`), thousands separators
+/// (`1,234,567`, `1 234 567`, `1'234'567`), percent (`12.5%`), parenthesized negatives (`(42)`),
+/// and magnitude suffixes (`1.2K`, `3M`, `4B`). Percent stays as written, so `12.5%` is `12.5`.
+pub fn parse_number_with_unit(
+	cell: &str,
+	decimal_comma: bool,
+) -> Option<(f64, Option<NumberUnit>)> {
+	let mut text = cell.trim();
+	if !decimal_comma {
+		if let Ok(found) = text.parse::<f64>() {
+			// Also parses "NaN" and "inf", which are not measured values
+			return Some((found, None)).filter(|_| found.is_finite());
+		}
+	}
+
+	let mut is_negative = false;
+	if let Some(inner) = text
+		.strip_prefix('(')
+		.and_then(|found| found.strip_suffix(')'))
+	{
+		is_negative = true;
+		text = inner.trim();
+	}
+	if let Some(inner) = text.strip_prefix('-') {
+		is_negative = !is_negative;
+		text = inner.trim_start();
+	}
+
+	let mut unit = NumberUnit::default();
+	if let Some(symbol) = CURRENCY_SYMBOLS.iter().find(|each| text.starts_with(*each)) {
+		unit.prefix = symbol.to_string();
+		text = text[symbol.len()..].trim_start();
+	}
+	if let Some(symbol) = CURRENCY_SYMBOLS.iter().find(|each| text.ends_with(*each)) {
+		unit.suffix = symbol.to_string();
+		text = text[..text.len() - symbol.len()].trim_end();
+	}
+	if let Some(inner) = text.strip_suffix('%') {
+		unit.suffix = String::from("%");
+		text = inner.trim_end();
+	}
+
+	let mut scale = 1.0;
+	if let Some((suffix, found)) = MAGNITUDE_SUFFIXES
+		.iter()
+		.find(|(suffix, _)| text.ends_with(*suffix) || text.ends_with(suffix.to_ascii_lowercase()))
+	{
+		unit.magnitude = Some(*suffix);
+		scale = *found;
+		text = text[..text.len() - 1].trim_end();
+	}
+
+	// Minus sign may also come after the currency symbol, e.g. $-12
+	if let Some(inner) = text.strip_prefix('-') {
+		is_negative = !is_negative;
+		text = inner;
+	}
+
+	let (thousands_separator, decimal_separator) = match decimal_comma {
+		true => ('.', ','),
+		false => (',', '.'),
+	};
+	let (integer_part, fraction_part) = match text.split_once(decimal_separator) {
+		Some((integer, fraction)) => (integer, Some(fraction)),
+		None => (text, None),
+	};
+
+	// Every group after the first one must be 3 digits, so "1,2,3" is not a number
+	let groups = integer_part
+		.split(|each: char| {
+			each == thousands_separator || each == ' ' || each == '\u{a0}' || each == '\''
+		})
+		.collect::<Vec<_>>();
+	let is_digits = |group: &str| group.chars().all(|each| each.is_ascii_digit());
+	let is_grouping_valid = match groups.as_slice() {
+		[single] => is_digits(single),
+		[first, rest @ ..] => {
+			!first.is_empty()
+				&& first.len() <= 3
+				&& is_digits(first)
+				&& rest.iter().all(|each| each.len() == 3 && is_digits(each))
+		}
+		[] => false,
+	};
+	if !is_grouping_valid {
+		return None;
+	}
+
+	let mut normalized = groups.concat();
+	if let Some(fraction) = fraction_part {
+		if fraction.is_empty() || !fraction.chars().all(|each| each.is_ascii_digit()) {
+			return None;
+		}
+		normalized = format!("{}.{}", normalized, fraction);
+	}
+
+	let value = normalized.parse::<f64>().ok()? * scale;
+	if !value.is_finite() {
+		return None;
+	}
+
+	let unit = Some(unit).filter(|found| *found != NumberUnit::default());
+	match is_negative {
+		true => Some((-value, unit)),
+		false => Some((value, unit)),
+	}
+}
+
+/// Spreadsheet has no time zone, its date/time is the wall clock of the chosen one. Date alone is
+/// at midnight, time alone is on 1970-01-01.
+fn parse_spreadsheet_cell(
+	cell: &calamine::Data,
+	cell_format: &CellFormat,
+) -> (CellValue, Option<NumberUnit>) {
+	if let (calamine::Data::String(text), Some(_)) = (cell, &cell_format.datetime_pattern) {
+		return parse_cell_with_unit(text, cell_format);
+	}
+
+	if let Some(number_value) = cell.as_f64() {
+		return (number_cell(number_value, cell_format), None);
+	}
+
+	let datetime_value = cell
+		.as_datetime()
+		.or_else(|| Some(cell.as_date()?.and_time(NaiveTime::MIN)))
+		.or_else(|| Some(DateTime::UNIX_EPOCH.date_naive().and_time(cell.as_time()?)));
+	match datetime_value {
+		Some(found) => match cell_format.zone.localize(&found) {
+			Some(found) => (CellValue::DateTime(found), None),
+			None => (CellValue::String(cell.to_string()), None), // Skipped by DST
+		},
+		None => parse_cell_with_unit(&cell.to_string(), cell_format),
+	}
+}
+
+/// Combines the header rows of a worksheet into one name per column. Merged cells only hold their
+/// value in the first cell, so a blank cell of the upper rows carries the name on its left. `None`
+/// if the worksheet ends before the header.
+fn combine_sheet_headers(
+	sheet: &calamine::Range<calamine::Data>,
+	sheet_layout: &SheetLayout,
+) -> Option<Vec<String>> {
+	let header_rows = sheet
+		.rows()
+		.skip(sheet_layout.header_row)
+		.take(sheet_layout.header_rows.max(1))
+		.collect::<Vec<_>>();
+	if header_rows.is_empty() {
+		return None;
+	}
+
+	let last_level = header_rows.len() - 1;
+	let mut carried = vec![String::new(); last_level];
+	let column_names = (0..sheet.width())
+		.map(|column_index| {
+			let parts = header_rows
+				.iter()
+				.enumerate()
+				.filter_map(|(level, each_row)| {
+					let text = each_row
+						.get(column_index)
+						.map(|cell| cell.to_string().trim().to_string())
+						.unwrap_or_default();
+					if level < last_level {
+						if !text.is_empty() {
+							carried[level] = text;
+						}
+						return Some(carried[level].clone()).filter(|found| !found.is_empty());
+					}
+
+					Some(text).filter(|found| !found.is_empty())
+				})
+				.collect::<Vec<_>>();
+
+			let name = match sheet_layout.header_combine {
+				HeaderCombine::Join => parts.join(&sheet_layout.header_separator),
+				HeaderCombine::First => parts.first().cloned().unwrap_or_default(),
+				HeaderCombine::Last => parts.last().cloned().unwrap_or_default(),
+			};
+			match name.is_empty() {
+				true => format!("Column {}", column_index + 1),
+				false => name,
+			}
+		})
+		.collect::<Vec<_>>();
+
+	Some(column_names)
+}
+
+/// Reads every row of the table, Arrow types are mapped into cells without re-parsing them.
+fn read_arrow_rows(table: &ArrowTable, cell_formats: &[CellFormat]) -> Vec<Vec<CellValue>> {
+	let mut rows = Vec::<Vec<CellValue>>::new();
+	for each_batch in &table.batches {
+		let columns = each_batch
+			.columns()
+			.iter()
+			.map(cast_arrow_column)
+			.collect::<Vec<_>>();
+
+		for row_index in 0..each_batch.num_rows() {
+			rows.push(
+				columns
+					.iter()
+					.zip(cell_formats)
+					.map(|(each, cell_format)| parse_arrow_cell(each, row_index, cell_format))
+					.collect::<Vec<_>>(),
+			);
+		}
+	}
+
+	rows
+}
+
+fn cast_arrow_column(array: &ArrayRef) -> ArrowColumn {
+	let casted = match array.data_type() {
+		ArrowType::Boolean => Ok(ArrowColumn::Boolean(array.as_boolean().clone())),
+		ArrowType::Timestamp(_, timezone) => cast(
+			array,
+			&ArrowType::Timestamp(TimeUnit::Millisecond, timezone.clone()),
+		)
+		.map(|found| {
+			ArrowColumn::DateTime(
+				found.as_primitive::<TimestampMillisecondType>().clone(),
+				timezone.is_some(),
+			)
+		}),
+		ArrowType::Date32 | ArrowType::Date64 => {
+			cast(array, &ArrowType::Timestamp(TimeUnit::Millisecond, None)).map(|found| {
+				ArrowColumn::DateTime(
+					found.as_primitive::<TimestampMillisecondType>().clone(),
+					false,
+				)
+			})
+		}
+		ArrowType::Utf8 | ArrowType::LargeUtf8 | ArrowType::Utf8View => {
+			cast(array, &ArrowType::Utf8)
+				.map(|found| ArrowColumn::Text(found.as_string::<i32>().clone()))
+		}
+		each if each.is_numeric() => cast(array, &ArrowType::Float64)
+			.map(|found| ArrowColumn::Number(found.as_primitive::<Float64Type>().clone())),
+		_ => Ok(ArrowColumn::Other(array.clone())),
+	};
+
+	casted.unwrap_or_else(|_| ArrowColumn::Other(array.clone()))
+}
+
+/// Null cell becomes an empty string, so its row will be dropped like an empty CSV cell.
+fn parse_arrow_cell(column: &ArrowColumn, index: usize, cell_format: &CellFormat) -> CellValue {
+	match column {
+		ArrowColumn::Number(array) if array.is_valid(index) => {
+			number_cell(array.value(index), cell_format)
+		}
+		ArrowColumn::Boolean(array) if array.is_valid(index) => {
+			CellValue::Boolean(array.value(index))
+		}
+		ArrowColumn::DateTime(array, has_timezone) if array.is_valid(index) => {
+			match DateTime::from_timestamp_millis(array.value(index)) {
+				Some(found) if *has_timezone => {
+					CellValue::DateTime(cell_format.zone.convert(&found))
+				}
+				Some(found) => match cell_format.zone.localize(&found.naive_utc()) {
+					Some(localized) => CellValue::DateTime(localized),
+					None => CellValue::String(found.naive_utc().to_string()),
+				},
+				None => CellValue::default(),
+			}
+		}
+		ArrowColumn::Text(array) if array.is_valid(index) => {
+			parse_cell(array.value(index), cell_format)
+		}
+		ArrowColumn::Other(array) if array.is_valid(index) => {
+			CellValue::String(array_value_to_string(array, index).unwrap_or_default())
+		}
+		_ => CellValue::default(),
+	}
+}
+
+/// Column type straight from the schema, or `None` if the text inside has to be checked.
+fn arrow_column_type(data_type: &ArrowType) -> Option<ColumnType> {
+	match data_type {
+		ArrowType::Boolean => Some(ColumnType::BOOLEAN),
+		ArrowType::Timestamp(_, _) | ArrowType::Date32 | ArrowType::Date64 => {
+			Some(ColumnType::DATETIME)
+		}
+		ArrowType::Utf8 | ArrowType::LargeUtf8 | ArrowType::Utf8View => None,
+		each if each.is_numeric() => Some(ColumnType::NUMBER),
+		_ => Some(ColumnType::STRING),
+	}
+}
+
+/// Cell as written, which also tells the series apart.
+fn cell_to_text(cell: &CellValue) -> String {
+	match cell {
+		CellValue::String(found) => found.trim().to_string(),
+		CellValue::Number(found) => found.to_string(),
+		CellValue::RowID(found) => found.to_string(),
+		CellValue::DateTime(found) => found.to_rfc3339(),
+		CellValue::Boolean(found) => found.to_string(),
+	}
+}
+
+/// Time zone chosen in `datetime_format`, or the local one if unset. Its pattern is checked too.
+pub fn resolve_zone(datetime_format: &DatetimeFormat) -> Result<Zone, ErrorInfo> {
+	if let Some(pattern) = &datetime_format.format {
+		if StrftimeItems::new(pattern).any(|each| each == Item::Error) {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_DATETIME_FORMAT),
+				message: format!("Cannot recognize \"{}\" as strftime pattern", pattern),
+			});
+		}
+	}
+
+	match &datetime_format.timezone {
+		Some(name) => match name.trim().parse::<Tz>() {
+			Ok(found) => Ok(Zone::Named(found)),
+			Err(_) => Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_DATETIME_FORMAT),
+				message: format!(
+					"Cannot recognize \"{}\" time zone, expected IANA name such as \"Europe/Berlin\" or \"UTC\"",
+					name
+				),
+			}),
+		},
+		None => Ok(Zone::Local),
+	}
+}
+
+/// Parsing context of each column, only the column named in `datetime_format` gets its pattern.
+fn column_cell_formats(
+	column_names: &[String],
+	datetime_format: &DatetimeFormat,
+	zone: Zone,
+	decimal_comma: bool,
+) -> Vec<CellFormat> {
+	column_names
+		.iter()
+		.map(|name| {
+			let is_datetime_column = datetime_format
+				.column
+				.as_ref()
+				.is_some_and(|found| **found == name.to_lowercase());
+			CellFormat {
+				decimal_comma,
+				datetime_pattern: datetime_format
+					.format
+					.clone()
+					.filter(|_| is_datetime_column),
+				numeric_timestamp: datetime_format.numeric.filter(|_| is_datetime_column),
+				zone,
+			}
+		})
+		.collect::<Vec<_>>()
+}
+
+/// Number cell, or date/time cell if its column holds numeric timestamps.
+fn number_cell(value: f64, cell_format: &CellFormat) -> CellValue {
+	match cell_format.numeric_timestamp {
+		Some(numeric) => match numeric.to_datetime(value, cell_format.zone) {
+			Some(found) => CellValue::DateTime(found),
+			None => CellValue::String(value.to_string()),
+		},
+		None => CellValue::Number(value),
+	}
+}
+
+/// Guesses which number column holds Unix epoch or Excel serial timestamps. Only a column named
+/// like a time is guessed, since IDs, prices or counts are easy to confuse with timestamps by range
+/// alone; other columns need `DatetimeFormat.numeric` chosen by the user.
+pub fn detect_numeric_timestamp(
+	column_names: &[String],
+	column_types: &[ColumnType],
+	cells: &[Vec<CellValue>],
+) -> Option<(usize, NumericTimestamp)> {
+	column_types
+		.iter()
+		.enumerate()
+		.filter(|(_, column_type)| **column_type == ColumnType::NUMBER)
+		.filter(|(index, _)| {
+			column_names
+				.get(*index)
+				.is_some_and(|name| is_named_as_time(&name.to_lowercase()))
+		})
+		.find_map(|(index, _)| {
+			let values = cells
+				.iter()
+				.filter_map(|each_row| match each_row.get(index)? {
+					CellValue::Number(found) => Some(*found),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			let min_value = values.iter().cloned().reduce(f64::min)?;
+			let max_value = values.iter().cloned().reduce(f64::max)?;
+			let (numeric, _, _) = TIMESTAMP_RANGES
+				.iter()
+				.find(|(_, start, end)| *start <= min_value && max_value < *end)?;
+
+			Some((index, *numeric))
+		})
+}
+
+/// Whether the lowercase column name reads as a time, e.g. `timestamp`, `ts` or `created_at`
+fn is_named_as_time(name: &str) -> bool {
+	TIMESTAMP_NAME_HINTS.iter().any(|each| name.contains(each))
+		|| name == "ts"
+		|| name.ends_with("_at")
+		|| name.ends_with("_ts")
+}
+
+impl NumericTimestamp {
+	/// Epoch based ones are the same instant everywhere, Excel serial is the wall clock of `zone`.
+	pub fn to_datetime(&self, value: f64, zone: Zone) -> Option<DateTime<FixedOffset>> {
+		if !value.is_finite() {
+			return None;
+		}
+
+		match self {
+			NumericTimestamp::Seconds => {
+				DateTime::from_timestamp_millis((value * 1e3).round() as i64)
+					.map(|found| zone.convert(&found))
+			}
+			NumericTimestamp::Milliseconds => DateTime::from_timestamp_millis(value.round() as i64)
+				.map(|found| zone.convert(&found)),
+			NumericTimestamp::Microseconds => DateTime::from_timestamp_micros(value.round() as i64)
+				.map(|found| zone.convert(&found)),
+			NumericTimestamp::ExcelSerial => {
+				let millis = ((value - EXCEL_UNIX_EPOCH_DAYS) * 86400e3).round() as i64;
+				zone.localize(&DateTime::from_timestamp_millis(millis)?.naive_utc())
+			}
+		}
+	}
+}
+
+impl SheetLayout {
+	/// Index of the first data row, counted the same way as `header_row`
+	pub fn data_start(&self) -> usize {
+		self.header_row + self.header_rows.max(1) + self.skip_rows
+	}
+}
+
+impl NumberUnit {
+	/// Writes the value back the way it was read, e.g. 1200000 with `$` and M is `$1.2M`
+	pub fn format(&self, value: f64) -> String {
+		let magnitude = MAGNITUDE_SUFFIXES
+			.iter()
+			.find(|(suffix, _)| Some(*suffix) == self.magnitude);
+
+		match magnitude {
+			Some((suffix, scale)) => {
+				format!("{}{}{}{}", self.prefix, value / scale, suffix, self.suffix)
+			}
+			None => format!("{}{}{}", self.prefix, value, self.suffix),
+		}
+	}
+}
+
+impl Zone {
+	/// Same instant, seen from this time zone
+	pub fn convert<T: TimeZone>(&self, datetime: &DateTime<T>) -> DateTime<FixedOffset> {
+		match self {
+			Zone::Local => datetime.with_timezone(&Local).fixed_offset(),
+			Zone::Named(timezone) => datetime.with_timezone(timezone).fixed_offset(),
+		}
+	}
+
+	pub fn from_timestamp(&self, timestamp: i64) -> Option<DateTime<FixedOffset>> {
+		DateTime::from_timestamp(timestamp, 0).map(|found| self.convert(&found))
+	}
+
+	/// Wall clock in this time zone, the earlier one wins when DST repeats it, `None` when DST
+	/// skips it.
+	pub fn localize(&self, datetime: &NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+		match self {
+			Zone::Local => datetime
+				.and_local_timezone(Local)
+				.earliest()
+				.map(|found| found.fixed_offset()),
+			Zone::Named(timezone) => datetime
+				.and_local_timezone(*timezone)
+				.earliest()
+				.map(|found| found.fixed_offset()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn open_sample_csv() -> SourceData {
+		let mut content = String::from("date,price,code,note\n");
+		for day in 1..=12 {
+			content.push_str(&format!("2024-01-{:02},{}.5,{},ok\n", day, day, day * 10));
+		}
+
+		SourceData::Csv(open_csv(content.as_bytes(), &Default::default()).unwrap())
+	}
+
+	fn read_sample_sheet(source_data: &mut SourceData, datetime_format: &DatetimeFormat) -> usize {
+		read_sheet(
+			source_data,
+			"",
+			datetime_format,
+			&HashMap::new(),
+			&SheetLayout::default(),
+			&HashMap::new(),
+		)
+		.unwrap()
+		.rows
+		.len()
+	}
+
+	/// Date/time, number, boolean and text columns, each in its own native type
+	fn sample_record_batch() -> arrow::record_batch::RecordBatch {
+		use arrow::array::{BooleanArray, Float32Array, Int64Array, StringArray};
+		use arrow::array::{Date32Array, TimestampMillisecondArray};
+
+		let start = 1_704_067_200_000; // 2024-01-01 at midnight
+		arrow::record_batch::RecordBatch::try_from_iter([
+			(
+				"time",
+				Arc::new(TimestampMillisecondArray::from_iter_values(
+					(0..12).map(|day| start + day * 86_400_000),
+				)) as ArrayRef,
+			),
+			(
+				"day",
+				Arc::new(Date32Array::from_iter_values(19_723..19_735)),
+			),
+			(
+				"price",
+				Arc::new(Float32Array::from_iter_values(
+					(0..12).map(|each| each as f32 + 0.5),
+				)),
+			),
+			("count", Arc::new(Int64Array::from_iter_values(0..12))),
+			(
+				"open",
+				Arc::new(BooleanArray::from_iter(
+					(0..12).map(|each| Some(each % 2 == 0)),
+				)),
+			),
+			(
+				"note",
+				Arc::new(StringArray::from_iter_values(
+					(0..12).map(|each| format!("n{}", each)),
+				)),
+			),
+		])
+		.unwrap()
+	}
+
+	fn assert_native_types(source_path: &Path) {
+		let mut source_data = open_source_data(source_path, &Default::default()).unwrap();
+		let content = read_sheet(
+			&mut source_data,
+			"",
+			&Default::default(),
+			&HashMap::new(),
+			&SheetLayout::default(),
+			&HashMap::new(),
+		)
+		.unwrap();
+		std::fs::remove_file(source_path).unwrap();
+
+		let column_types = content
+			.columns
+			.iter()
+			.map(|each| each.column_type)
+			.collect::<Vec<_>>();
+		assert_eq!(
+			column_types,
+			vec![
+				ColumnType::DATETIME,
+				ColumnType::DATETIME,
+				ColumnType::NUMBER,
+				ColumnType::NUMBER,
+				ColumnType::BOOLEAN,
+				ColumnType::STRING,
+			]
+		);
+		assert_eq!(content.rows.len(), 12);
+		assert!(matches!(
+			content.rows[0][&Arc::new(String::from("price"))],
+			CellValue::Number(found) if found == 0.5
+		));
+	}
+
+	fn sniff(bytes: &[u8]) -> CsvDialect {
+		sniff_csv_dialect(bytes, &Default::default()).unwrap().1
+	}
+
+	#[test]
+	fn csv_can_be_read_again() {
+		let mut source_data = open_sample_csv();
+
+		assert_eq!(read_sample_sheet(&mut source_data, &Default::default()), 12);
+
+		let datetime_format = DatetimeFormat {
+			column: Some(Arc::new(String::from("date"))),
+			format: Some(String::from("%Y-%m-%d")),
+			..Default::default()
+		};
+		assert_eq!(read_sample_sheet(&mut source_data, &datetime_format), 12);
+	}
+
+	#[test]
+	fn csv_column_type_can_be_overridden_after_reading() {
+		let mut source_data = open_sample_csv();
+		assert_eq!(read_sample_sheet(&mut source_data, &Default::default()), 12);
+
+		let code = Arc::new(String::from("code"));
+		let type_overrides = HashMap::from([(code.clone(), ColumnType::STRING)]);
+		let content = read_sheet(
+			&mut source_data,
+			"",
+			&Default::default(),
+			&type_overrides,
+			&SheetLayout::default(),
+			&HashMap::new(),
+		)
+		.unwrap();
+
+		assert_eq!(content.rows.len(), 12);
+		let column = &content.columns[content.column_lookup[&code]];
+		assert_eq!(column.column_type, ColumnType::STRING);
+	}
+
+	#[test]
+	fn parquet_keeps_its_native_types() {
+		let source_path = std::env::temp_dir().join("project-tarot-native-types.parquet");
+		let record_batch = sample_record_batch();
+		let mut writer = parquet::arrow::ArrowWriter::try_new(
+			File::create(&source_path).unwrap(),
+			record_batch.schema(),
+			None,
+		)
+		.unwrap();
+		writer.write(&record_batch).unwrap();
+		writer.close().unwrap();
+
+		assert_native_types(&source_path);
+	}
+
+	#[test]
+	fn arrow_ipc_keeps_its_native_types() {
+		let source_path = std::env::temp_dir().join("project-tarot-native-types.arrow");
+		let record_batch = sample_record_batch();
+		let mut writer = arrow::ipc::writer::FileWriter::try_new(
+			File::create(&source_path).unwrap(),
+			&record_batch.schema(),
+		)
+		.unwrap();
+		writer.write(&record_batch).unwrap();
+		writer.finish().unwrap();
+
+		assert_native_types(&source_path);
+	}
+
+	#[test]
+	fn delimiter_is_sniffed() {
+		assert_eq!(sniff(b"date,price\n2024-01-01,1.5\n").delimiter, ',');
+		assert_eq!(
+			sniff(b"date;price;note\n2024-01-01;1.5;a,b\n").delimiter,
+			';'
+		);
+		assert_eq!(sniff(b"date\tprice\n2024-01-01\t1.5\n").delimiter, '\t');
+		assert_eq!(sniff(b"date|price\n2024-01-01|1.5\n").delimiter, '|');
+	}
+
+	#[test]
+	fn quote_is_sniffed() {
+		assert_eq!(sniff(b"name,price\n\"a, b\",1.5\n").quote, '"');
+		assert_eq!(sniff(b"name,price\n'a',1.5\n'b',2.5\n").quote, '\'');
+	}
+
+	#[test]
+	fn header_is_sniffed() {
+		assert!(sniff(b"date,price\n2024-01-01,1.5\n").has_header);
+		assert!(!sniff(b"2024-01-01,1.5\n2024-01-02,2.5\n").has_header);
+	}
+
+	#[test]
+	fn encoding_is_sniffed() {
+		let dialect = sniff(b"\xEF\xBB\xBFdate,price\n2024-01-01,1.5\n");
+		assert_eq!(dialect.encoding, "utf-8");
+
+		// "é" in Latin-1 is not valid UTF-8
+		let (content, dialect) =
+			sniff_csv_dialect(b"caf\xE9,price\nx,1.5\n", &Default::default()).unwrap();
+		assert_eq!(dialect.encoding, "windows-1252");
+		assert!(content.starts_with("café,"));
+	}
+
+	#[test]
+	fn decimal_comma_is_sniffed() {
+		let dialect = sniff(b"date;price\n2024-01-01;1,5\n2024-01-02;1.234,5\n");
+		assert_eq!(dialect.delimiter, ';');
+		assert!(dialect.decimal_comma);
+
+		// Comma as delimiter cannot be the decimal separator at the same time
+		assert!(!sniff(b"date,price\n2024-01-01,1.5\n").decimal_comma);
+	}
+
+	#[test]
+	fn chosen_dialect_is_not_sniffed() {
+		let csv_dialect = CsvDialectOverride {
+			delimiter: Some(';'),
+			has_header: Some(false),
+			encoding: Some(String::from("latin1")),
+			..Default::default()
+		};
+		let (_, dialect) = sniff_csv_dialect(b"date,price\n", &csv_dialect).unwrap();
+
+		assert_eq!(dialect.delimiter, ';');
+		assert!(!dialect.has_header);
+		assert_eq!(dialect.encoding, "windows-1252");
+	}
+
+	#[test]
+	fn unknown_encoding_is_rejected() {
+		let csv_dialect = CsvDialectOverride {
+			encoding: Some(String::from("klingon")),
+			..Default::default()
+		};
+
+		assert!(sniff_csv_dialect(b"date,price\n", &csv_dialect).is_err());
+	}
+
+	#[test]
+	fn number_with_unit_is_parsed() {
+		let unit = |prefix: &str, suffix: &str| NumberUnit {
+			prefix: prefix.to_string(),
+			suffix: suffix.to_string(),
+			magnitude: None,
+		};
+
+		assert_eq!(
+			parse_number_with_unit("1234.5", false),
+			Some((1234.5, None))
+		);
+		assert_eq!(
+			parse_number_with_unit("$1,234.50", false),
+			Some((1234.5, Some(unit("$", ""))))
+		);
+		assert_eq!(
+			parse_number_with_unit("12,50 €", true),
+			Some((12.5, Some(unit("", "€"))))
+		);
+		assert_eq!(
+			parse_number_with_unit("(12.5%)", false),
+			Some((-12.5, Some(unit("", "%"))))
+		);
+		assert_eq!(
+			parse_number_with_unit("1'234'567", false),
+			Some((1234567.0, None))
+		);
+		assert_eq!(
+			parse_number_with_unit("$1.5k", false),
+			Some((
+				1500.0,
+				Some(NumberUnit {
+					magnitude: Some('K'),
+					..unit("$", "")
+				})
+			))
+		);
+		assert_eq!(
+			parse_number_with_unit("1.234,5", true),
+			Some((1234.5, None))
+		);
+	}
+
+	#[test]
+	fn number_with_bad_grouping_is_rejected() {
+		assert_eq!(parse_number_with_unit("1,2,3", false), None);
+		assert_eq!(parse_number_with_unit("12,5", false), None);
+		assert_eq!(parse_number_with_unit("abc", false), None);
+		assert_eq!(parse_number_with_unit("", false), None);
+	}
+
+	#[test]
+	fn number_which_is_not_finite_is_rejected() {
+		for text in ["NaN", "nan", "inf", "-inf", "infinity", "1e400"] {
+			assert_eq!(parse_number_with_unit(text, false), None);
+		}
+	}
+
+	#[test]
+	fn number_is_written_with_its_unit() {
+		let unit = NumberUnit {
+			prefix: String::from("$"),
+			suffix: String::new(),
+			magnitude: Some('M'),
+		};
+		assert_eq!(unit.format(1.5e6), "$1.5M");
+
+		let unit = NumberUnit {
+			suffix: String::from("%"),
+			..Default::default()
+		};
+		assert_eq!(unit.format(12.5), "12.5%");
+	}
+
+	#[test]
+	fn numeric_timestamp_is_detected() {
+		let column_names = vec![String::from("id"), String::from("created_at")];
+		let column_types = vec![ColumnType::NUMBER, ColumnType::NUMBER];
+		let cells = (0..10)
+			.map(|index| {
+				vec![
+					CellValue::Number(1e9 + index as f64),
+					CellValue::Number(1.7e12 + index as f64 * 1000.0),
+				]
+			})
+			.collect::<Vec<_>>();
+
+		// Whole numbers in range of an epoch stay numbers unless the name says time
+		assert_eq!(
+			detect_numeric_timestamp(&column_names, &column_types, &cells),
+			Some((1, NumericTimestamp::Milliseconds))
+		);
+		assert_eq!(
+			detect_numeric_timestamp(&column_names[..1], &column_types[..1], &cells),
+			None
+		);
+	}
+
+	#[test]
+	fn numeric_timestamp_needs_a_plausible_range() {
+		let column_names = vec![String::from("price")];
+		let column_types = vec![ColumnType::NUMBER];
+		let cells = vec![vec![CellValue::Number(12.5)], vec![CellValue::Number(1e9)]];
+
+		assert_eq!(
+			detect_numeric_timestamp(&column_names, &column_types, &cells),
+			None
+		);
+	}
+
+	#[test]
+	fn multi_row_header_is_combined() {
+		let mut sheet = calamine::Range::new((0, 0), (2, 2));
+		sheet.set_value((0, 0), calamine::Data::String(String::from("Title")));
+		sheet.set_value((1, 1), calamine::Data::String(String::from("Sales")));
+		sheet.set_value((2, 0), calamine::Data::String(String::from("Date")));
+		sheet.set_value((2, 1), calamine::Data::String(String::from("Q1")));
+		sheet.set_value((2, 2), calamine::Data::String(String::from("Q2")));
+
+		let mut sheet_layout = SheetLayout {
+			header_row: 1,
+			header_rows: 2,
+			..Default::default()
+		};
+		assert_eq!(
+			combine_sheet_headers(&sheet, &sheet_layout),
+			Some(vec![
+				String::from("Date"),
+				String::from("Sales Q1"),
+				String::from("Sales Q2"),
+			])
+		);
+
+		sheet_layout.header_combine = HeaderCombine::First;
+		assert_eq!(
+			combine_sheet_headers(&sheet, &sheet_layout),
+			Some(vec![
+				String::from("Date"),
+				String::from("Sales"),
+				String::from("Sales"),
+			])
+		);
+
+		sheet_layout.header_row = 3;
+		assert_eq!(combine_sheet_headers(&sheet, &sheet_layout), None);
+	}
+
+	#[test]
+	fn csv_missing_values_can_be_imputed_after_reading() {
+		let mut content = String::from("date,price\n");
+		for day in 1..=12 {
+			match day {
+				6 => content.push_str("2024-01-06,\n"),
+				_ => content.push_str(&format!("2024-01-{:02},{}.5\n", day, day)),
+			}
+		}
+		let mut source_data =
+			SourceData::Csv(open_csv(content.as_bytes(), &Default::default()).unwrap());
+		assert_eq!(read_sample_sheet(&mut source_data, &Default::default()), 11);
+
+		let missing_values = HashMap::from([(
+			Arc::new(String::from("price")),
+			MissingValueStrategy::ForwardFill,
+		)]);
+		let content = read_sheet(
+			&mut source_data,
+			"",
+			&Default::default(),
+			&HashMap::new(),
+			&SheetLayout::default(),
+			&missing_values,
+		)
+		.unwrap();
+
+		assert_eq!(content.rows.len(), 12);
+	}
+
+	fn open_sample_sqlite() -> SourceData {
+		let connection = Connection::open_in_memory().unwrap();
+		connection
+			.execute("CREATE TABLE prices (date TEXT, price REAL, note TEXT)", [])
+			.unwrap();
+		for day in 1..=12 {
+			connection
+				.execute(
+					"INSERT INTO prices VALUES (?1, ?2, 'ok')",
+					(format!("2024-01-{:02}", day), day as f64 + 0.5),
+				)
+				.unwrap();
+		}
+
+		SourceData::Sqlite(connection)
+	}
+
+	fn read_sqlite_sheet(source_data: &mut SourceData, tab_name: &str) -> SheetContent {
+		read_sheet(
+			source_data,
+			tab_name,
+			&Default::default(),
+			&HashMap::new(),
+			&SheetLayout::default(),
+			&HashMap::new(),
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn sqlite_table_is_read() {
+		let mut source_data = open_sample_sqlite();
+		assert_eq!(
+			list_tabs(&source_data).unwrap(),
+			Some(vec![String::from("prices")])
+		);
+
+		let content = read_sqlite_sheet(&mut source_data, "prices");
+		let column_types = content
+			.columns
+			.iter()
+			.map(|each| each.column_type)
+			.collect::<Vec<_>>();
+		assert_eq!(
+			column_types,
+			vec![ColumnType::DATETIME, ColumnType::NUMBER, ColumnType::STRING]
+		);
+		assert_eq!(content.rows.len(), 12);
+	}
+
+	#[test]
+	fn sqlite_custom_query_is_read() {
+		let mut source_data = open_sample_sqlite();
+		let content = read_sqlite_sheet(
+			&mut source_data,
+			"SELECT date, price * 2 AS doubled FROM prices WHERE price > 1",
+		);
+
+		let fields = content
+			.columns
+			.iter()
+			.map(|each| each.field.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(fields, vec!["date", "doubled"]);
+		assert_eq!(content.rows.len(), 12);
+		assert_eq!(*content.selected_predictable_column, "doubled");
+		assert!(matches!(
+			content.rows[0][&Arc::new(String::from("doubled"))],
+			CellValue::Number(found) if found == 3.0
+		));
+
+		let found = read_sheet(
+			&mut source_data,
+			"SELECT * FROM missing_table",
+			&Default::default(),
+			&HashMap::new(),
+			&SheetLayout::default(),
+			&HashMap::new(),
+		);
+		assert!(found.is_err());
+	}
+
+	#[test]
+	fn json_nested_keys_are_flattened() {
+		let source_path = std::env::temp_dir().join("project-tarot-nested.json");
+		let rows = (1..=12)
+			.map(|day| {
+				format!(
+					r#"{{"date":"2024-01-{:02}","price":{{"close":{}.5,"open":{}}},"tags":["a",{}]}}"#,
+					day, day, day, day
+				)
+			})
+			.collect::<Vec<_>>();
+		std::fs::write(&source_path, format!("[{}]", rows.join(","))).unwrap();
+
+		let mut source_data = open_source_data(&source_path, &Default::default()).unwrap();
+		std::fs::remove_file(&source_path).unwrap();
+		let content = read_sheet(
+			&mut source_data,
+			"",
+			&Default::default(),
+			&HashMap::new(),
+			&SheetLayout::default(),
+			&HashMap::new(),
+		)
+		.unwrap();
+
+		let columns = content
+			.columns
+			.iter()
+			.map(|each| (each.header_name.as_str(), each.column_type))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			columns,
+			vec![
+				("date", ColumnType::DATETIME),
+				("price.close", ColumnType::NUMBER),
+				("price.open", ColumnType::NUMBER),
+				("tags", ColumnType::STRING),
+			]
+		);
+
+		// Arrays are kept as their JSON text
+		assert!(matches!(
+			&content.rows[0][&Arc::new(String::from("tags"))],
+			CellValue::String(found) if found == r#"["a",1]"#
+		));
+	}
+
+	#[test]
+	fn ndjson_missing_keys_can_be_imputed() {
+		let source_path = std::env::temp_dir().join("project-tarot-missing.ndjson");
+		let lines = (1..=12)
+			.map(|day| match day {
+				6 => String::from(r#"{"date":"2024-01-06"}"#),
+				_ => format!(r#"{{"date":"2024-01-{:02}","price":{}.5}}"#, day, day),
+			})
+			.collect::<Vec<_>>();
+		std::fs::write(&source_path, lines.join("\n")).unwrap();
+
+		let mut source_data = open_source_data(&source_path, &Default::default()).unwrap();
+		std::fs::remove_file(&source_path).unwrap();
+		assert_eq!(read_sample_sheet(&mut source_data, &Default::default()), 11);
+
+		let missing_values = HashMap::from([(
+			Arc::new(String::from("price")),
+			MissingValueStrategy::Linear,
+		)]);
+		let content = read_sheet(
+			&mut source_data,
+			"",
+			&Default::default(),
+			&HashMap::new(),
+			&SheetLayout::default(),
+			&missing_values,
+		)
+		.unwrap();
+
+		assert_eq!(content.rows.len(), 12);
+		assert!(content.dropped_row_indices.is_empty());
+	}
+
+	#[test]
+	fn datetime_text_without_offset_is_in_chosen_zone() {
+		let cell_format = CellFormat {
+			zone: Zone::Named(chrono_tz::Europe::Berlin),
+			..Default::default()
+		};
+
+		for text in [
+			"2024-07-01 12:00",
+			"2024-07-01T12:00:00",
+			"2024/07/01 12:00:00.000",
+		] {
+			let found = parse_datetime_text(text, &cell_format).unwrap();
+			assert_eq!(found.to_rfc3339(), "2024-07-01T12:00:00+02:00");
+		}
+		let found = parse_datetime_text("01 Jul 2024", &cell_format).unwrap();
+		assert_eq!(found.to_rfc3339(), "2024-07-01T00:00:00+02:00");
+	}
+
+	#[test]
+	fn datetime_text_with_offset_is_converted() {
+		let cell_format = CellFormat {
+			zone: Zone::Named(chrono_tz::Europe::Berlin),
+			..Default::default()
+		};
+
+		for text in [
+			"2024-07-01T12:00:00Z",
+			"2024-07-01T14:00:00+02:00",
+			"2024-07-01 13:00:00+01:00",
+		] {
+			let found = parse_datetime_text(text, &cell_format).unwrap();
+			assert_eq!(found.to_rfc3339(), "2024-07-01T14:00:00+02:00");
+		}
+		assert!(parse_datetime_text("12:00 tomorrow", &cell_format).is_none());
+		assert!(parse_datetime_text("2024-13-01", &cell_format).is_none());
+	}
+
+	#[test]
+	fn datetime_before_epoch_is_rejected() {
+		let rows = vec![(None, 0, vec![1.0]), (None, 3600, vec![2.0])];
+		assert_eq!(group_series(rows).unwrap()[0].points.len(), 2);
+
+		let rows = vec![(None, -3600, vec![1.0]), (None, 0, vec![2.0])];
+		assert!(group_series(rows).is_err());
+	}
+}
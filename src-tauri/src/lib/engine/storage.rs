@@ -0,0 +1,617 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use burn::{
+	backend::{ndarray::NdArrayDevice, NdArray},
+	module::Module,
+	record::{FullPrecisionSettings, NamedMpkBytesRecorder, Record, Recorder},
+	tensor::backend::Backend,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	borrow::Cow,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+use strum::IntoEnumIterator;
+
+use super::model::*;
+use super::preprocess::{
+	aggregate_duplicate_timestamps, build_historical_data_like, impute_missing_values,
+};
+use super::source::{extract_series, open_source_data, read_sheet};
+use super::typedef::*;
+
+/// File extension of a saved model, without the dot
+pub const MODEL_EXTENSION: &'static str = "tarotmodel";
+
+/// File extension of a saved project, without the dot
+pub const PROJECT_EXTENSION: &'static str = "tarot";
+
+const ERROR_SAVE: &'static str = "Cannot Save Model";
+const ERROR_LOAD: &'static str = "Cannot Load Model";
+const ERROR_SAVE_PROJECT: &'static str = "Cannot Save Project";
+const ERROR_LOAD_PROJECT: &'static str = "Cannot Open Project";
+
+/// Version is bumped whenever [`ModelRecord`] changes in an incompatible way
+const MODEL_HEADER: FileHeader = FileHeader {
+	magic: *b"TAROTMDL",
+	version: 1,
+	name: "Model",
+};
+
+/// Version is bumped whenever [`ProjectRecord`], [`ProjectInfo`], or [`ModelRecord`] inside it
+/// changes in an incompatible way
+const PROJECT_HEADER: FileHeader = FileHeader {
+	magic: *b"TAROTPRJ",
+	version: 1,
+	name: "Project",
+};
+
+/// Written in front of the record, so an unsupported version is told before decoding the rest
+struct FileHeader {
+	magic: [u8; 8],
+	version: u32, // Little endian, right after `magic`
+	name: &'static str,
+}
+
+/// Content of a model file, everything besides the weights is stored as plain primitives
+#[derive(Record)]
+struct ModelRecord<B: Backend> {
+	network: <LstmNetwork<B> as Module<B>>::Record,
+	network_config: String, // JSON of LstmNetworkConfig
+	mean: Vec<f64>,         // One per column
+	stdev: Vec<f64>,        // One per column
+	metrics: String,        // JSON of ColumnMetrics, one per predicted column
+	first_timestamp: u64,
+	timestamp_interval: u32,
+	sequence_size: u32,
+	batch_periode: String,
+	datetime_column: String,
+	predictable_column: String,
+	target_columns: Vec<String>,
+	feature_columns: Vec<String>,
+	series_column: Option<String>,
+	series_ids: Vec<String>,
+}
+
+/// Content of a project file, everything besides the model is stored as JSON of [`ProjectInfo`]
+#[derive(Record)]
+struct ProjectRecord<B: Backend> {
+	project_info: String,
+	source_copy: Option<Vec<u8>>,
+	model: Option<ModelRecord<B>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectInfo {
+	source_path: PathBuf,
+	#[serde(default)]
+	csv_dialect: CsvDialectOverride,
+	preprocess_config: Option<PreprocessConfig>,
+	#[serde(default)]
+	train_settings: Option<TrainSettings>,
+	forecast: Option<Forecast>,
+	#[serde(default)]
+	backtest: Option<Backtest>,
+}
+
+/// Writes the weights and everything needed to forecast without training into a single file.
+pub fn save_model(trained_model: &TrainedModel, target_path: &Path) -> Result<(), ErrorInfo> {
+	let record = into_model_record(trained_model, ERROR_SAVE)?;
+	write_record(record, &MODEL_HEADER, target_path, ERROR_SAVE)
+}
+
+/// Reads a file written by [`save_model`].
+pub fn load_model(source_path: &Path) -> Result<TrainedModel, ErrorInfo> {
+	let record: ModelRecord<NdArray> = read_record(source_path, &MODEL_HEADER, ERROR_LOAD)?;
+	from_model_record(record, ERROR_LOAD)
+}
+
+/// Writes the whole session into a single file, the model is stored the same way as
+/// [`save_model`].
+pub fn save_project(project: &Project, target_path: &Path) -> Result<(), ErrorInfo> {
+	let project_info = ProjectInfo {
+		source_path: project.source_path.clone(),
+		csv_dialect: project.csv_dialect.clone(),
+		preprocess_config: project.preprocess_config.clone(),
+		train_settings: project.train_settings.clone(),
+		forecast: project.forecast.clone(),
+		backtest: project.backtest.clone(),
+	};
+
+	let project_info = match serde_json::to_string(&project_info) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_SAVE_PROJECT),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	let model = match &project.trained_model {
+		Some(found) => Some(into_model_record(found, ERROR_SAVE_PROJECT)?),
+		None => None,
+	};
+
+	let record = ProjectRecord::<NdArray> {
+		project_info,
+		source_copy: project.source_copy.clone(),
+		model,
+	};
+
+	write_record(record, &PROJECT_HEADER, target_path, ERROR_SAVE_PROJECT)
+}
+
+/// Reads a file written by [`save_project`].
+pub fn load_project(source_path: &Path) -> Result<Project, ErrorInfo> {
+	let record: ProjectRecord<NdArray> =
+		read_record(source_path, &PROJECT_HEADER, ERROR_LOAD_PROJECT)?;
+
+	let project_info: ProjectInfo = match serde_json::from_str(&record.project_info) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(ERROR_LOAD_PROJECT),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	let trained_model = match record.model {
+		Some(found) => Some(from_model_record(found, ERROR_LOAD_PROJECT)?),
+		None => None,
+	};
+
+	Ok(Project {
+		source_path: project_info.source_path,
+		source_copy: record.source_copy,
+		csv_dialect: project_info.csv_dialect,
+		preprocess_config: project_info.preprocess_config,
+		train_settings: project_info.train_settings,
+		trained_model,
+		forecast: project_info.forecast,
+		backtest: project_info.backtest,
+	})
+}
+
+/// Path of the historical data file of a project. If the original file has gone, the embedded
+/// copy is extracted into temporary directory instead.
+pub fn restore_source_path(project: &Project) -> Result<PathBuf, ErrorInfo> {
+	if project.source_path.is_file() {
+		return Ok(project.source_path.clone());
+	}
+
+	let source_copy = match &project.source_copy {
+		Some(found) => found,
+		None => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed("Historical Data Missing"),
+				message: format!(
+					"Because \"{}\" does not exist anymore and the project has no copy of it",
+					project.source_path.to_string_lossy()
+				),
+			})
+		}
+	};
+
+	let file_name = project
+		.source_path
+		.file_name()
+		.map(|name| name.to_os_string())
+		.unwrap_or("historical_data.csv".into());
+	let extract_dir = std::env::temp_dir().join("project-tarot");
+	let extract_path = extract_dir.join(file_name);
+
+	if let Err(err) = std::fs::create_dir_all(&extract_dir)
+		.and_then(|_| std::fs::write(&extract_path, source_copy))
+	{
+		return Err(ErrorInfo {
+			title: Cow::Borrowed("Cannot Extract Historical Data"),
+			message: err.to_string(),
+		});
+	}
+
+	Ok(extract_path)
+}
+
+/// Reads the historical data of a project again with its submitted config, as if it was just
+/// submitted on Preprocess page. The series are re-sampled like `trained_model` was, so it can be
+/// backtested and its forecast exported without training.
+pub fn reopen_source(
+	source_path: &Path,
+	csv_dialect: &CsvDialectOverride,
+	config: &PreprocessConfig,
+	trained_model: &TrainedModel,
+) -> Result<ReopenedSource, ErrorInfo> {
+	let mut source_data = open_source_data(source_path, csv_dialect)?;
+	let content = read_sheet(
+		&mut source_data,
+		config.tab_name.as_deref().unwrap_or_default(),
+		&config.datetime_format,
+		&config.column_types,
+		&config.sheet_layout,
+		&config.missing_values,
+	)?;
+
+	let mut selected_source_data = extract_series(
+		&mut source_data,
+		&content.column_lookup,
+		&content.dropped_row_indices,
+		config,
+	)?;
+	impute_missing_values(&mut selected_source_data, config);
+	aggregate_duplicate_timestamps(&mut selected_source_data, config.duplicate_aggregation);
+	let preprocessed_data =
+		build_historical_data_like(selected_source_data, config, trained_model)?;
+
+	Ok(ReopenedSource {
+		source_data,
+		content,
+		preprocessed_data,
+	})
+}
+
+fn into_model_record(
+	trained_model: &TrainedModel,
+	error_title: &'static str,
+) -> Result<ModelRecord<NdArray>, ErrorInfo> {
+	let network_config = match serde_json::to_string(&trained_model.network_config) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(error_title),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	let metrics = match serde_json::to_string(&trained_model.metrics) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(error_title),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	Ok(ModelRecord::<NdArray> {
+		network: trained_model.network.clone().into_record(),
+		network_config,
+		mean: trained_model.normal_param.mean.clone(),
+		stdev: trained_model.normal_param.stdev.clone(),
+		metrics,
+		first_timestamp: trained_model.first_timestamp,
+		timestamp_interval: trained_model.timestamp_interval,
+		sequence_size: trained_model.sequence_size,
+		batch_periode: trained_model.batch_periode.to_string(),
+		datetime_column: trained_model.datetime_column.to_string(),
+		predictable_column: trained_model.predictable_column.to_string(),
+		target_columns: trained_model
+			.target_columns
+			.iter()
+			.map(|each| each.to_string())
+			.collect(),
+		feature_columns: trained_model
+			.feature_columns
+			.iter()
+			.map(|each| each.to_string())
+			.collect(),
+		series_column: trained_model
+			.series_column
+			.as_ref()
+			.map(|each| each.to_string()),
+		series_ids: trained_model
+			.series_ids
+			.iter()
+			.map(|each| each.to_string())
+			.collect(),
+	})
+}
+
+fn from_model_record(
+	record: ModelRecord<NdArray>,
+	error_title: &'static str,
+) -> Result<TrainedModel, ErrorInfo> {
+	let device = NdArrayDevice::Cpu;
+
+	let network_config: LstmNetworkConfig = match serde_json::from_str(&record.network_config) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(error_title),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	let metrics: Vec<ColumnMetrics> = match serde_json::from_str(&record.metrics) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(error_title),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	let batch_periode =
+		match BatchPeriode::iter().find(|each| each.to_string() == record.batch_periode) {
+			Some(found) => found,
+			None => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(error_title),
+					message: format!("Unknown batch periode \"{}\"", record.batch_periode),
+				})
+			}
+		};
+
+	let network = network_config
+		.init::<NdArray>(&device)
+		.load_record(record.network);
+
+	Ok(TrainedModel {
+		network,
+		network_config,
+		normal_param: NormalParam {
+			mean: record.mean,
+			stdev: record.stdev,
+		},
+		metrics,
+		first_timestamp: record.first_timestamp,
+		timestamp_interval: record.timestamp_interval,
+		sequence_size: record.sequence_size,
+		batch_periode,
+		datetime_column: Arc::new(record.datetime_column),
+		predictable_column: Arc::new(record.predictable_column),
+		target_columns: record.target_columns.into_iter().map(Arc::new).collect(),
+		feature_columns: record.feature_columns.into_iter().map(Arc::new).collect(),
+		series_column: record.series_column.map(Arc::new),
+		series_ids: record.series_ids.into_iter().map(Arc::new).collect(),
+	})
+}
+
+fn write_record<R: Record<NdArray>>(
+	record: R,
+	header: &FileHeader,
+	target_path: &Path,
+	error_title: &'static str,
+) -> Result<(), ErrorInfo> {
+	let record_bytes =
+		match NamedMpkBytesRecorder::<FullPrecisionSettings>::new().record(record, ()) {
+			Ok(ok) => ok,
+			Err(err) => {
+				return Err(ErrorInfo {
+					title: Cow::Borrowed(error_title),
+					message: err.to_string(),
+				})
+			}
+		};
+
+	let mut bytes = Vec::with_capacity(header.magic.len() + 4 + record_bytes.len());
+	bytes.extend_from_slice(&header.magic);
+	bytes.extend_from_slice(&header.version.to_le_bytes());
+	bytes.extend_from_slice(&record_bytes);
+
+	if let Err(err) = std::fs::write(target_path, bytes) {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(error_title),
+			message: err.to_string(),
+		});
+	}
+
+	Ok(())
+}
+
+fn read_record<R: Record<NdArray>>(
+	source_path: &Path,
+	header: &FileHeader,
+	error_title: &'static str,
+) -> Result<R, ErrorInfo> {
+	let device = NdArrayDevice::Cpu;
+
+	let mut bytes = match std::fs::read(source_path) {
+		Ok(ok) => ok,
+		Err(err) => {
+			return Err(ErrorInfo {
+				title: Cow::Borrowed(error_title),
+				message: err.to_string(),
+			})
+		}
+	};
+
+	// Version is checked first, since older or newer records may not even decode
+	let version_start = header.magic.len();
+	let record_start = version_start + 4;
+	if bytes.len() < record_start || bytes[..version_start] != header.magic {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(error_title),
+			message: format!(
+				"Not a valid {} file or it is corrupted",
+				header.name.to_lowercase()
+			),
+		});
+	}
+
+	let mut version = [0u8; 4];
+	version.copy_from_slice(&bytes[version_start..record_start]);
+	let version = u32::from_le_bytes(version);
+	if version != header.version {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(error_title),
+			message: format!(
+				"{} file version {} is not supported, expected version {}",
+				header.name, version, header.version
+			),
+		});
+	}
+
+	let record_bytes = bytes.split_off(record_start);
+	match NamedMpkBytesRecorder::<FullPrecisionSettings>::new().load(record_bytes, &device) {
+		Ok(ok) => Ok(ok),
+		Err(err) => Err(ErrorInfo {
+			title: Cow::Borrowed(error_title),
+			message: format!("Not a valid file or it is corrupted: {}", err),
+		}),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn sample_model() -> TrainedModel {
+		let network_config = TrainSettings {
+			hidden_size: 4,
+			..Default::default()
+		}
+		.network_config(2, 0);
+
+		TrainedModel {
+			network: network_config.init(&NdArrayDevice::Cpu),
+			network_config,
+			normal_param: NormalParam {
+				mean: vec![1.5, 2.5],
+				stdev: vec![0.5, 1.0],
+			},
+			metrics: Vec::new(),
+			first_timestamp: 1_700_000_000,
+			timestamp_interval: 3600,
+			sequence_size: 24,
+			batch_periode: BatchPeriode::DAILY,
+			datetime_column: Arc::new(String::from("date")),
+			predictable_column: Arc::new(String::from("price")),
+			target_columns: Vec::new(),
+			feature_columns: vec![Arc::new(String::from("volume"))],
+			series_column: None,
+			series_ids: Vec::new(),
+		}
+	}
+
+	fn network_bytes(trained_model: &TrainedModel) -> Vec<u8> {
+		NamedMpkBytesRecorder::<FullPrecisionSettings>::new()
+			.record(trained_model.network.clone().into_record(), ())
+			.unwrap()
+	}
+
+	#[test]
+	fn model_can_be_loaded_after_saved() {
+		let target_path = std::env::temp_dir().join("project-tarot-round-trip.tarotmodel");
+		let trained_model = sample_model();
+		save_model(&trained_model, &target_path).unwrap();
+		let loaded_model = load_model(&target_path).unwrap();
+		std::fs::remove_file(&target_path).unwrap();
+
+		assert_eq!(network_bytes(&loaded_model), network_bytes(&trained_model));
+		assert_eq!(
+			loaded_model.normal_param.mean,
+			trained_model.normal_param.mean
+		);
+		assert_eq!(
+			loaded_model.normal_param.stdev,
+			trained_model.normal_param.stdev
+		);
+		assert_eq!(loaded_model.first_timestamp, trained_model.first_timestamp);
+		assert_eq!(loaded_model.batch_periode, trained_model.batch_periode);
+		assert_eq!(loaded_model.feature_columns, trained_model.feature_columns);
+	}
+
+	#[test]
+	fn project_can_be_opened_after_saved() {
+		let target_path = std::env::temp_dir().join("project-tarot-round-trip.tarot");
+		let mut source_copy = String::from("date,price,volume\n");
+		for hour in 0..72 {
+			source_copy.push_str(&format!(
+				"2024-01-{:02} {:02}:00,{}.5,{}\n",
+				hour / 24 + 1,
+				hour % 24,
+				hour,
+				hour * 10
+			));
+		}
+
+		// Original file has gone, so the embedded copy is read instead
+		let project = Project {
+			source_path: PathBuf::from("gone/prices.csv"),
+			source_copy: Some(source_copy.into_bytes()),
+			csv_dialect: Default::default(),
+			preprocess_config: Some(PreprocessConfig {
+				tab_name: None,
+				datetime_column: Arc::new(String::from("date")),
+				predictable_column: Arc::new(String::from("price")),
+				feature_columns: vec![Arc::new(String::from("volume"))],
+				batch_periode: BatchPeriode::DAILY,
+				..Default::default()
+			}),
+			train_settings: None,
+			trained_model: Some(sample_model()),
+			forecast: None,
+			backtest: None,
+		};
+		save_project(&project, &target_path).unwrap();
+		let loaded_project = load_project(&target_path).unwrap();
+		std::fs::remove_file(&target_path).unwrap();
+
+		assert_eq!(loaded_project.source_path, project.source_path);
+		assert_eq!(loaded_project.source_copy, project.source_copy);
+		let trained_model = loaded_project.trained_model.as_ref().unwrap();
+		assert_eq!(
+			network_bytes(trained_model),
+			network_bytes(project.trained_model.as_ref().unwrap())
+		);
+
+		let source_path = restore_source_path(&loaded_project).unwrap();
+		let reopened = reopen_source(
+			&source_path,
+			&loaded_project.csv_dialect,
+			loaded_project.preprocess_config.as_ref().unwrap(),
+			trained_model,
+		)
+		.unwrap();
+
+		let price = Arc::new(String::from("price"));
+		assert!(reopened.content.column_lookup.contains_key(&price));
+		assert_eq!(reopened.preprocessed_data.len(), 1);
+		assert!(!reopened.preprocessed_data[0].batches.is_empty());
+	}
+
+	#[test]
+	fn unsupported_version_is_told_before_decoding() {
+		let target_path = std::env::temp_dir().join("project-tarot-version.tarotmodel");
+		let mut bytes = MODEL_HEADER.magic.to_vec();
+		bytes.extend_from_slice(&2u32.to_le_bytes());
+		bytes.extend_from_slice(b"anything newer");
+		std::fs::write(&target_path, bytes).unwrap();
+
+		let found = load_model(&target_path);
+		let project = load_project(&target_path);
+		std::fs::remove_file(&target_path).unwrap();
+
+		assert_eq!(
+			found.err().unwrap().message,
+			"Model file version 2 is not supported, expected version 1"
+		);
+		assert_eq!(
+			project.err().unwrap().message,
+			"Not a valid project file or it is corrupted"
+		);
+	}
+}
@@ -0,0 +1,548 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use burn::{
+	backend::{ndarray::NdArrayDevice, Autodiff, NdArray},
+	data::{
+		dataloader::{batcher::Batcher, DataLoaderBuilder},
+		dataset::InMemDataset,
+	},
+	grad_clipping::GradientClippingConfig,
+	module::AutodiffModule,
+	nn::loss::{MseLoss, Reduction::Mean},
+	optim::{AdamConfig, GradientsParams, Optimizer},
+	tensor::{
+		backend::{AutodiffBackend, Backend},
+		ElementConversion, Tensor,
+	},
+};
+
+use std::{
+	borrow::Cow,
+	sync::{atomic::Ordering, Arc},
+};
+
+use super::model::*;
+use super::progress::Progress;
+use super::typedef::*;
+
+const ERROR_SETTINGS: &'static str = "Invalid Training Settings";
+const ERROR_TOO_SHORT: &'static str = "Not Enough Data to Train";
+
+impl TrainSettings {
+	/// Rejects settings which would crash or never converge
+	pub fn validate(&self) -> Result<(), ErrorInfo> {
+		let message = if !(1..=1024).contains(&self.hidden_size) {
+			format!(
+				"Hidden size must be between 1 and 1024, got {}",
+				self.hidden_size
+			)
+		} else if !(1..=8).contains(&self.num_layers) {
+			format!(
+				"Number of layers must be between 1 and 8, got {}",
+				self.num_layers
+			)
+		} else if !(0f64..1f64).contains(&self.dropout) {
+			format!(
+				"Dropout must be at least 0 and less than 1, got {}",
+				self.dropout
+			)
+		} else if !(self.learning_rate > 0f64 && self.learning_rate <= 1f64) {
+			format!(
+				"Learning rate must be above 0 and at most 1, got {}",
+				self.learning_rate
+			)
+		} else if !(1..=4096).contains(&self.batch_size) {
+			format!(
+				"Batch size must be between 1 and 4096, got {}",
+				self.batch_size
+			)
+		} else if !(1..=100_000).contains(&self.max_epochs) {
+			format!(
+				"Maximum epochs must be between 1 and 100000, got {}",
+				self.max_epochs
+			)
+		} else if self
+			.clip_norm
+			.is_some_and(|norm| !(norm > 0f32 && norm.is_finite()))
+		{
+			format!(
+				"Clipping norm must be above 0, got {}",
+				self.clip_norm.unwrap()
+			)
+		} else if !(1..=100_000).contains(&self.patience) {
+			format!(
+				"Patience must be between 1 and 100000, got {}",
+				self.patience
+			)
+		} else if !(self.min_delta >= 0f32 && self.min_delta.is_finite()) {
+			format!("Minimum delta must be at least 0, got {}", self.min_delta)
+		} else if self.min_epochs > self.max_epochs {
+			format!(
+				"Minimum epochs ({}) cannot exceed maximum epochs ({})",
+				self.min_epochs, self.max_epochs
+			)
+		} else {
+			return Ok(());
+		};
+
+		Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_SETTINGS),
+			message,
+		})
+	}
+
+	/// The network takes every column (plus the series embedding) as input and predicts the next
+	/// value of every column, so its own prediction can be fed back when forecasting further ahead.
+	pub fn network_config(&self, column_count: usize, series_count: usize) -> LstmNetworkConfig {
+		LstmNetworkConfig::new()
+			.with_input_size(column_count + series_count)
+			.with_output_size(column_count)
+			.with_hidden_size(self.hidden_size)
+			.with_num_layers(self.num_layers)
+			.with_dropout(self.dropout)
+			.with_bidirectional(self.bidirectional)
+	}
+}
+
+impl TrainControl {
+	/// Stops training before the next epoch, the best model so far is still returned
+	pub fn cancel(&self) {
+		self.canceled.store(true, Ordering::SeqCst);
+		self.resume();
+	}
+
+	/// Holds training before the next epoch until [`Self::resume`] or [`Self::cancel`]
+	pub fn pause(&self) {
+		if let Ok(mut paused) = self.paused.lock() {
+			*paused = true;
+		}
+	}
+
+	pub fn resume(&self) {
+		if let Ok(mut paused) = self.paused.lock() {
+			*paused = false;
+		}
+		self.resumed.notify_all();
+	}
+
+	pub fn is_canceled(&self) -> bool {
+		self.canceled.load(Ordering::SeqCst)
+	}
+
+	pub fn is_paused(&self) -> bool {
+		self.paused.lock().map(|paused| *paused).unwrap_or(false)
+	}
+
+	/// Blocks as long as it is paused, then tells whether training should go on
+	fn should_continue(&self) -> bool {
+		if let Ok(paused) = self.paused.lock() {
+			let _resumed = self.resumed.wait_while(paused, |paused| *paused);
+		}
+
+		!self.is_canceled()
+	}
+}
+
+/// Trains a new model from scratch on CPU, this blocks until training is done or canceled through
+/// `control`. Panel data trains one model shared by every series. The settings are expected to be
+/// validated already. Rejects data which cannot spare a sequence for validation, since the best
+/// weights could not be told apart from the random ones.
+pub fn train(
+	preprocessed_data: &[HistoricalData],
+	settings: &TrainSettings,
+	control: &TrainControl,
+	progress: &mut impl Progress,
+) -> Result<TrainedModel, ErrorInfo> {
+	let device = NdArrayDevice::Cpu;
+
+	let random_seed: u64 = rand::random();
+	Autodiff::<NdArray>::seed(random_seed);
+
+	// Only panel data has something to tell apart
+	let series_ids = match settings.series_embedding {
+		true => preprocessed_data
+			.iter()
+			.filter_map(|each| each.series_id.clone())
+			.collect::<Vec<_>>(),
+		false => Vec::new(),
+	};
+
+	let input = prepare_datasets(preprocessed_data, &series_ids);
+	if input.train_items.is_empty() || input.valid_items.is_empty() {
+		return Err(ErrorInfo {
+			title: Cow::Borrowed(ERROR_TOO_SHORT),
+			message: format!(
+				"Need at least 2 sequences in a series, one for training and one for validation, got {}",
+				input.train_items.len() + input.valid_items.len()
+			),
+		});
+	}
+
+	let normal_param = NormalParam {
+		mean: input.mean.clone(),
+		stdev: input.stdev.clone(),
+	};
+
+	let network_config = settings.network_config(input.mean.len(), input.series_count);
+	let fallback_series = HistoricalData::default();
+	let first_series = preprocessed_data.first().unwrap_or(&fallback_series);
+	let (network, metrics) =
+		train_new_model::<Autodiff<NdArray>>(input, settings, control, &device, progress);
+
+	Ok(TrainedModel {
+		network,
+		network_config,
+		normal_param,
+		metrics,
+		first_timestamp: preprocessed_data
+			.iter()
+			.map(|each| each.first_timestamp)
+			.min()
+			.unwrap_or_default(),
+		timestamp_interval: first_series.timestamp_interval,
+		sequence_size: first_series
+			.batches
+			.first()
+			.map(|each| each.len() as u32)
+			.unwrap_or(1),
+		batch_periode: first_series.batch_periode,
+		datetime_column: first_series.datetime_column.clone(),
+		predictable_column: first_series.predictable_column.clone(),
+		target_columns: first_series.target_columns.clone(),
+		feature_columns: first_series.feature_columns.clone(),
+		series_column: first_series.series_column.clone(),
+		series_ids,
+	})
+}
+
+/// Index of the first validation sequence of a series, the sequences before it are for training.
+/// 80% for training but leave at least one for each side if possible.
+pub fn validation_start(batch_count: usize) -> usize {
+	(batch_count * 8 / 10)
+		.max(1)
+		.min(batch_count.saturating_sub(1))
+}
+
+/// Normalizes each column of the batches of every series, then splits each series into training
+/// and validation sequences. When `series_ids` is not empty, each step also gets the one-hot
+/// embedding of its series appended.
+pub fn prepare_datasets(
+	preprocessed_data: &[HistoricalData],
+	series_ids: &[Arc<String>],
+) -> TrainInput {
+	let column_count = preprocessed_data
+		.iter()
+		.flat_map(|each| each.batches.first())
+		.flat_map(|each_sequence| each_sequence.first())
+		.map(|each_step| each_step.len())
+		.next()
+		.unwrap_or(1);
+
+	// Calculate mean and stdev of each column for normalization, to prevent training from
+	// diverging and a column with large values from drowning the others
+	let all_steps = || {
+		preprocessed_data
+			.iter()
+			.flat_map(|each| each.batches.iter())
+			.flatten()
+	};
+	let flatten_length = all_steps().count();
+	let mut mean = Vec::<f64>::with_capacity(column_count);
+	let mut stdev = Vec::<f64>::with_capacity(column_count);
+	for column_index in 0..column_count {
+		let sum = all_steps().fold(0f64, |last_value, each| last_value + each[column_index]);
+		let column_mean = sum / flatten_length as f64;
+		let deviations = all_steps().fold(0f64, |last_value, each| {
+			last_value + (each[column_index] - column_mean).powf(2f64)
+		});
+		let column_stdev = (deviations / (flatten_length as f64 - 1f64)).sqrt();
+
+		mean.push(column_mean);
+		// A constant column has no deviation, dividing by it would give NaN
+		stdev.push(if column_stdev > 0f64 && column_stdev.is_finite() {
+			column_stdev
+		} else {
+			1f64
+		});
+	}
+
+	let mut train_items = Vec::<SequenceItem>::new();
+	let mut valid_items = Vec::<SequenceItem>::new();
+	for each_series in preprocessed_data {
+		let batch_count = each_series.batches.len();
+		let embedding = series_embedding(series_ids, each_series.series_id.as_ref());
+
+		// The last step of each sequence is the target, the rest is the input
+		let mut all_items = each_series
+			.batches
+			.iter()
+			.map(|each_sequence| {
+				let mut input = each_sequence
+					.iter()
+					.map(|each_step| {
+						each_step
+							.iter()
+							.zip(mean.iter().zip(stdev.iter()))
+							.map(|(each, (mean, stdev))| (*each - mean) / stdev)
+							.collect::<Vec<_>>()
+					})
+					.collect::<Vec<_>>();
+				let target = input.pop().unwrap_or_default();
+				input
+					.iter_mut()
+					.for_each(|each_step| each_step.extend_from_slice(&embedding));
+
+				SequenceItem { input, target }
+			})
+			.collect::<Vec<_>>();
+
+		let slice_line = validation_start(batch_count);
+		valid_items.extend(all_items.split_off(slice_line));
+		train_items.extend(all_items);
+	}
+
+	// Every series has the same columns
+	let predicted_columns = preprocessed_data
+		.first()
+		.map(|each| {
+			let mut predicted_columns = vec![each.predictable_column.clone()];
+			predicted_columns.extend(each.target_columns.iter().cloned());
+			predicted_columns
+		})
+		.unwrap_or_default();
+
+	TrainInput {
+		train_items,
+		valid_items,
+		mean,
+		stdev,
+		series_count: series_ids.len(),
+		predicted_columns,
+	}
+}
+
+/// One-hot vector telling which of `series_ids` it is, all zero for an unknown series
+pub fn series_embedding(series_ids: &[Arc<String>], series_id: Option<&Arc<String>>) -> Vec<f64> {
+	series_ids
+		.iter()
+		.map(|each| match series_id {
+			Some(found) if found == each => 1f64,
+			_ => 0f64,
+		})
+		.collect::<Vec<_>>()
+}
+
+impl<B: Backend> Batcher<B, SequenceItem, SequenceBatch<B>> for SequenceBatcher {
+	fn batch(&self, items: Vec<SequenceItem>, device: &B::Device) -> SequenceBatch<B> {
+		let inputs = items
+			.iter()
+			.map(|each| {
+				let flatten_input = each.input.iter().flatten().cloned().collect::<Vec<_>>();
+				let input_size = each.input.first().map(|each_step| each_step.len());
+
+				Tensor::<B, 1>::from_floats(flatten_input.as_slice(), device).reshape([
+					1,
+					each.input.len(),
+					input_size.unwrap_or(each.target.len()),
+				])
+			})
+			.collect::<Vec<_>>();
+
+		let targets = items
+			.iter()
+			.map(|each| {
+				Tensor::<B, 1>::from_floats(each.target.as_slice(), device).unsqueeze_dim(0)
+			})
+			.collect::<Vec<_>>();
+
+		SequenceBatch {
+			inputs: Tensor::cat(inputs, 0),
+			targets: Tensor::cat(targets, 0),
+		}
+	}
+}
+
+/// Returns the best model with its metrics. Each epoch goes through all training sequences in
+/// shuffled mini-batches, then stops once validation loss has not improved for `patience` epochs.
+/// The metrics measure each predicted column, which come first in every step.
+pub fn train_new_model<B: AutodiffBackend>(
+	input: TrainInput,
+	settings: &TrainSettings,
+	control: &TrainControl,
+	device: &B::Device,
+	progress: &mut impl Progress,
+) -> (LstmNetwork<B::InnerBackend>, Vec<ColumnMetrics>) {
+	let config = TrainingConfig::new(
+		settings.network_config(input.mean.len(), input.series_count),
+		// Gradient clipping via optimizer config
+		AdamConfig::new().with_grad_clipping(settings.clip_norm.map(GradientClippingConfig::Norm)),
+	)
+	.with_lr(settings.learning_rate);
+
+	let valid_num_items = input.valid_items.len();
+	let predicted_columns = input.predicted_columns;
+	let (mean, stdev) = (input.mean, input.stdev);
+
+	let train_loader = DataLoaderBuilder::<B, _, SequenceBatch<B>>::new(SequenceBatcher)
+		.batch_size(settings.batch_size)
+		.shuffle(rand::random())
+		.set_device(device.clone())
+		.build(InMemDataset::new(input.train_items));
+	let valid_loader =
+		DataLoaderBuilder::<B::InnerBackend, _, SequenceBatch<B::InnerBackend>>::new(
+			SequenceBatcher,
+		)
+		.batch_size(settings.batch_size)
+		.set_device(device.clone())
+		.build(InMemDataset::new(input.valid_items));
+
+	let mut model = config.model.init::<B>(device);
+	let mut optim = config.optimizer.init::<B, LstmNetwork<B>>();
+
+	// Copy of the weights with the lowest validation loss so far
+	let mut best_model = model.valid();
+	let mut best_valid_loss = f32::INFINITY;
+	let mut best_epoch = 0u32;
+	let mut best_metrics = Vec::<ColumnMetrics>::new();
+	let mut stop_reason = StopReason::MaxEpochs;
+
+	for epoch in 1u32..=settings.max_epochs {
+		if !control.should_continue() {
+			stop_reason = StopReason::Canceled;
+			break;
+		}
+
+		// Initialize the training and validation metrics at the start of each epoch
+		let mut valid_loss = 0f32;
+
+		// Training phase, one optimizer step per mini-batch
+		for batch in train_loader.iter() {
+			let output = model.forward(&batch.inputs, None).0;
+			let loss = MseLoss::new().forward(output, batch.targets, Mean);
+
+			// Gradients for the current backward pass
+			let grads = loss.backward();
+			// Gradients linked to each parameter of the model
+			let grads = GradientsParams::from_grads(grads, &model);
+			// Update the model using the optimizer
+			model = optim.step(config.lr, model, grads);
+		}
+
+		// Validation phase, also keeps denormalized values of the predicted columns for the metrics
+		let valid_model = model.valid();
+		let mut actual = vec![Vec::<f64>::with_capacity(valid_num_items); predicted_columns.len()];
+		let mut predicted = actual.clone();
+		for batch in valid_loader.iter() {
+			let batch_len = batch.targets.dims()[0];
+			let output = valid_model.forward(&batch.inputs, None).0;
+
+			let targets = batch.targets.to_data().iter::<f64>().collect::<Vec<_>>();
+			let outputs = output.to_data().iter::<f64>().collect::<Vec<_>>();
+			for (column_index, (each_actual, each_predicted)) in
+				actual.iter_mut().zip(predicted.iter_mut()).enumerate()
+			{
+				let denormalize = |y: &f64| y * stdev[column_index] + mean[column_index];
+				each_actual.extend(
+					targets
+						.iter()
+						.skip(column_index)
+						.step_by(mean.len())
+						.map(denormalize),
+				);
+				each_predicted.extend(
+					outputs
+						.iter()
+						.skip(column_index)
+						.step_by(mean.len())
+						.map(denormalize),
+				);
+			}
+
+			let loss = MseLoss::new().forward(output, batch.targets, Mean);
+			valid_loss += loss.into_scalar().elem::<f32>() * batch_len as f32;
+		}
+
+		// The averaged validation loss per epoch
+		let avg_valid_loss = valid_loss / valid_num_items as f32;
+
+		// Display the averaged validation metrics
+		let new_point = IterativePoint {
+			x: epoch,
+			metrics: predicted_columns
+				.iter()
+				.zip(actual.iter().zip(predicted.iter()))
+				.map(|(column, (actual, predicted))| ColumnMetrics {
+					column: column.clone(),
+					metrics: ForecastMetrics::measure(actual, predicted),
+				})
+				.collect(),
+		};
+		progress.on_train_progress(new_point.clone());
+
+		// Keep the best weights, anything within min_delta is not an improvement
+		if avg_valid_loss < best_valid_loss - settings.min_delta {
+			best_model = valid_model;
+			best_valid_loss = avg_valid_loss;
+			best_epoch = epoch;
+			best_metrics = new_point.metrics;
+		}
+
+		if epoch >= settings.min_epochs && epoch - best_epoch >= settings.patience {
+			stop_reason = StopReason::NoImprovement;
+			break;
+		}
+	}
+
+	progress.on_train_stop(stop_reason, best_epoch);
+
+	(best_model, best_metrics)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn series_of(sequence_count: usize) -> HistoricalData {
+		HistoricalData {
+			batches: (0..sequence_count)
+				.map(|index| (0..4).map(|step| vec![(index + step) as f64]).collect())
+				.collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn each_split_keeps_a_sequence() {
+		let input = prepare_datasets(&[series_of(10)], &[]);
+		assert_eq!((input.train_items.len(), input.valid_items.len()), (8, 2));
+
+		let input = prepare_datasets(&[series_of(2)], &[]);
+		assert_eq!((input.train_items.len(), input.valid_items.len()), (1, 1));
+	}
+
+	#[test]
+	fn too_few_sequences_are_rejected() {
+		let settings = TrainSettings::default();
+		let control = TrainControl::default();
+
+		assert!(train(&[series_of(1)], &settings, &control, &mut ()).is_err());
+		assert!(train(&[series_of(1), series_of(1)], &settings, &control, &mut ()).is_err());
+		assert!(train(&[], &settings, &control, &mut ()).is_err());
+	}
+}
@@ -0,0 +1,695 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use arrow::{
+	array::{ArrayRef, BooleanArray, Float64Array, StringArray, TimestampMillisecondArray},
+	datatypes::SchemaRef,
+	record_batch::RecordBatch,
+};
+use burn::{
+	backend::NdArray,
+	optim::AdamConfig,
+	tensor::{backend::Backend, Tensor},
+};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::{
+	borrow::Cow,
+	collections::HashMap,
+	fs::File,
+	io::{BufReader, Cursor},
+	path::PathBuf,
+	sync::{atomic::AtomicBool, Arc, Condvar, Mutex},
+};
+use strum_macros::EnumIter;
+
+use super::model::*;
+
+/// Error of any engine step, the title is short enough to be a dialog title.
+#[derive(Default, Serialize, Clone, Debug)]
+pub struct ErrorInfo {
+	pub title: Cow<'static, str>,
+	pub message: String,
+}
+
+impl std::fmt::Display for ErrorInfo {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}: {}", self.title, self.message)
+	}
+}
+
+impl std::error::Error for ErrorInfo {}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum CellValue {
+	String(String),
+	Number(f64),
+	RowID(u32),
+	DateTime(DateTime<FixedOffset>), // Offset of the chosen time zone
+	Boolean(bool),
+}
+
+impl Default for CellValue {
+	fn default() -> Self {
+		CellValue::String(String::new())
+	}
+}
+
+/// Opened historical data file, see [`super::source::open_source_data`].
+#[derive(Default)]
+pub enum SourceData {
+	#[default]
+	None,
+	Csv(CsvSource),
+	Spreadsheet(calamine::Sheets<BufReader<File>>),
+	Parquet(ArrowTable),
+	Arrow(ArrowTable), // Arrow IPC, also known as Feather
+	Json(JsonTable),   // Either array of objects or newline-delimited ones
+	Sqlite(rusqlite::Connection),
+}
+
+/// CSV file decoded into UTF-8, read with the dialect it was written in.
+pub struct CsvSource {
+	pub reader: csv::Reader<Cursor<Vec<u8>>>,
+	pub dialect: CsvDialect,
+}
+
+/// How a CSV file is written, see [`super::source::sniff_csv_dialect`].
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvDialect {
+	pub delimiter: char,
+	pub quote: char,
+	pub has_header: bool,
+	pub encoding: String,    // Label such as "utf-8" or "windows-1252"
+	pub decimal_comma: bool, // 1.234,56 instead of 1,234.56
+}
+
+/// Fields chosen by user, the unset ones are sniffed from the file.
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvDialectOverride {
+	pub delimiter: Option<char>,
+	pub quote: Option<char>,
+	pub has_header: Option<bool>,
+	pub encoding: Option<String>,
+	pub decimal_comma: Option<bool>,
+}
+
+/// Where the header and the data begin in a worksheet, the other file types ignore this. Rows are
+/// counted from the first non-empty row of the worksheet, starting from zero.
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SheetLayout {
+	pub header_row: usize,  // Rows above it are skipped, such as a title block
+	pub header_rows: usize, // More than one if the header has several levels
+	pub skip_rows: usize,   // Between the header and the first data row
+	pub header_combine: HeaderCombine,
+	pub header_separator: String, // Only for HeaderCombine::Join
+}
+
+impl Default for SheetLayout {
+	fn default() -> Self {
+		Self {
+			header_row: 0,
+			header_rows: 1,
+			skip_rows: 0,
+			header_combine: HeaderCombine::Join,
+			header_separator: String::from(" "),
+		}
+	}
+}
+
+/// How a column name is made out of a multi-row header, see [`super::source::combine_sheet_headers`].
+#[derive(Default, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum HeaderCombine {
+	#[default]
+	Join, // "Sales" above "Q1" becomes "Sales Q1"
+	First, // Topmost filled cell
+	Last,  // Bottommost filled cell
+}
+
+/// How the date/time column is written, unset fields fall back to guessing in local time zone.
+#[derive(Default, PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DatetimeFormat {
+	pub column: Option<Arc<String>>, // Which column `format` and `numeric` apply to
+	pub format: Option<String>,      // strftime pattern such as "%d/%m/%Y %H:%M"
+	pub timezone: Option<String>,    // IANA name such as "Europe/Berlin", or "UTC"
+	pub numeric: Option<NumericTimestamp>, // Unit of `column` if it is written as number
+}
+
+/// What a number in the date/time column counts, see [`super::source::detect_numeric_timestamp`].
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum NumericTimestamp {
+	Seconds, // Since Unix epoch
+	Milliseconds,
+	Microseconds,
+	ExcelSerial, // Days since 1899-12-30, wall clock of the chosen time zone
+}
+
+/// Time zone of the date/time cells without offset, see [`super::source::resolve_zone`].
+#[derive(Default, Clone, Copy, Debug)]
+pub enum Zone {
+	#[default]
+	Local,
+	Named(chrono_tz::Tz),
+}
+
+/// How the text cells of one column are parsed, see [`super::source::parse_cell`].
+#[derive(Default, Clone, Debug)]
+pub struct CellFormat {
+	pub decimal_comma: bool,
+	pub datetime_pattern: Option<String>, // Only set for the date/time column
+	pub numeric_timestamp: Option<NumericTimestamp>, // Same as above
+	pub zone: Zone,
+}
+
+/// Whole columnar file loaded into memory, its schema already knows the type of each column.
+pub struct ArrowTable {
+	pub schema: SchemaRef,
+	pub batches: Vec<RecordBatch>,
+}
+
+#[derive(Default, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+	#[default]
+	STRING,
+	NUMBER,
+	#[serde(rename = "dateTime")]
+	DATETIME,
+	BOOLEAN,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnInfo {
+	pub field: Arc<String>,
+	pub header_name: String,
+	#[serde(rename = "type")]
+	pub column_type: ColumnType,
+	pub unit: Option<NumberUnit>, // Only set for number column written with a unit
+}
+
+/// Symbol written around the numbers of a column, e.g. `$` of `$123.45` or `%` of `12.5%`.
+#[derive(Default, PartialEq, Eq, Hash, Deserialize, Serialize, Clone, Debug)]
+pub struct NumberUnit {
+	pub prefix: String,
+	pub suffix: String,
+	pub magnitude: Option<char>, // K, M, B, or T right after the number, e.g. of `$1.2M`
+}
+
+#[derive(Default, PartialEq, Eq, EnumIter, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchPeriode {
+	MINUTELY,
+	HOURLY,
+	DAILY,
+	WEEKLY,
+	MONTHLY,
+	#[default]
+	YEARLY,
+}
+
+impl std::fmt::Display for BatchPeriode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			BatchPeriode::MINUTELY => write!(f, "minutely"),
+			BatchPeriode::HOURLY => write!(f, "hourly"),
+			BatchPeriode::DAILY => write!(f, "daily"),
+			BatchPeriode::WEEKLY => write!(f, "weekly"),
+			BatchPeriode::MONTHLY => write!(f, "monthly"),
+			BatchPeriode::YEARLY => write!(f, "yearly"),
+		}
+	}
+}
+
+#[derive(Default, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectionType {
+	#[default]
+	EXCLUDE,
+	INCLUDE,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, Debug)]
+pub struct RowSelection {
+	pub ids: Vec<u64>,
+	#[serde(rename = "type")]
+	pub selection_type: SelectionType,
+}
+
+/// Typed and cleaned content of a sheet, see [`super::source::read_sheet`].
+pub struct SheetContent {
+	pub columns: Vec<ColumnInfo>,
+	pub rows: Vec<HashMap<Arc<String>, CellValue>>,
+	pub selected_datetime_column: Arc<String>,
+	pub selected_predictable_column: Arc<String>,
+	pub column_lookup: HashMap<Arc<String>, usize>,
+	pub dropped_row_indices: Vec<u64>,
+	pub warning: Option<ErrorInfo>,
+	pub datetime_format: DatetimeFormat, // Including the detected numeric timestamp, if any
+	pub column_inferences: Vec<ColumnInference>, // Same order as columns
+}
+
+/// Why a column got its type, and which cells disagree with it so their rows were dropped.
+#[derive(Default, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnInference {
+	pub field: Arc<String>,
+	pub counts: ColumnCounter, // Before cleaning
+	pub is_overridden: bool,   // Chosen by user instead of the majority
+	pub is_imputed: bool,      // Offending cells are filled instead of dropping their rows
+	pub offending_count: u64,
+	pub offending_samples: Vec<OffendingCell>, // Only the first few
+}
+
+#[derive(Default, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OffendingCell {
+	pub row_id: u64,
+	pub value: String, // Empty if the row is too short
+}
+
+/// Flattened JSON objects whose nested keys become dotted column names like `sensor.temperature`,
+/// the values are only parsed once the date/time format is known.
+pub struct JsonTable {
+	pub column_names: Vec<String>,
+	pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// Cells which are already typed while being read, i.e. rows of a SQLite query.
+pub struct TypedTable {
+	pub column_names: Vec<String>,
+	pub rows: Vec<Vec<CellValue>>,
+	pub units: Vec<Option<NumberUnit>>, // One per column
+}
+
+/// Arrow array casted into the few shapes [`CellValue`] cares about.
+pub(super) enum ArrowColumn {
+	Number(Float64Array),
+	Boolean(BooleanArray),
+	DateTime(TimestampMillisecondArray, bool), // Whether it has time zone, otherwise wall clock
+	Text(StringArray),
+	Other(ArrayRef),
+}
+
+/// Occurence of each unit (or none) in each column, the most occuring one wins.
+#[derive(Default)]
+pub(super) struct UnitCounter {
+	pub counts: Vec<HashMap<Option<NumberUnit>, u64>>,
+}
+
+/// Cells of each type in one column.
+#[derive(Default, Serialize, Clone, Copy, Debug)]
+pub struct ColumnCounter {
+	pub string: u64,
+	pub number: u64,
+	pub datetime: u64,
+	pub boolean: u64,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PreprocessConfig {
+	pub tab_name: Option<String>, // None only if CSV
+	pub datetime_column: Arc<String>,
+	pub predictable_column: Arc<String>,
+	#[serde(default)]
+	pub target_columns: Vec<Arc<String>>, // Forecasted along with predictable_column
+	#[serde(default)]
+	pub feature_columns: Vec<Arc<String>>, // Extra inputs besides the predicted columns
+	#[serde(default)]
+	pub series_column: Option<Arc<String>>, // Splits rows into separate series, None if only one
+	pub batch_periode: BatchPeriode,
+	pub row_selection: RowSelection,
+	#[serde(default)]
+	pub datetime_format: DatetimeFormat,
+	#[serde(default)]
+	pub column_types: HashMap<Arc<String>, ColumnType>, // Overrides the majority vote
+	#[serde(default)]
+	pub sheet_layout: SheetLayout,
+	#[serde(default)]
+	pub missing_values: HashMap<Arc<String>, MissingValueStrategy>, // Drop if unset
+	#[serde(default)]
+	pub duplicate_aggregation: DuplicateAggregation,
+}
+
+/// How points sharing the same timestamp become one, see
+/// [`super::preprocess::aggregate_duplicate_timestamps`].
+#[derive(Default, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateAggregation {
+	#[default]
+	Mean,
+	Sum,
+	First, // In the order of the file
+	Last,
+	Min,
+	Max,
+}
+
+impl std::fmt::Display for DuplicateAggregation {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DuplicateAggregation::Mean => write!(f, "mean"),
+			DuplicateAggregation::Sum => write!(f, "sum"),
+			DuplicateAggregation::First => write!(f, "first"),
+			DuplicateAggregation::Last => write!(f, "last"),
+			DuplicateAggregation::Min => write!(f, "min"),
+			DuplicateAggregation::Max => write!(f, "max"),
+		}
+	}
+}
+
+/// How a missing or invalid cell of a number column is handled, see
+/// [`super::preprocess::impute_missing_values`].
+#[derive(Default, PartialEq, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase", tag = "type", content = "value")]
+pub enum MissingValueStrategy {
+	#[default]
+	Drop, // The whole row
+	ForwardFill,
+	BackwardFill,
+	Linear, // Between the neighbors, by their timestamp
+	Constant(f64),
+	Mean, // Of the same column in the same series
+}
+
+impl std::fmt::Display for MissingValueStrategy {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MissingValueStrategy::Drop => write!(f, "drop"),
+			MissingValueStrategy::ForwardFill => write!(f, "forward fill"),
+			MissingValueStrategy::BackwardFill => write!(f, "backward fill"),
+			MissingValueStrategy::Linear => write!(f, "linear interpolation"),
+			MissingValueStrategy::Constant(value) => write!(f, "constant {}", value),
+			MissingValueStrategy::Mean => write!(f, "mean"),
+		}
+	}
+}
+
+/// How many cells of a column were filled, the ones without a neighbor to fill from are dropped.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImputationSummary {
+	pub column: Arc<String>,
+	pub strategy: MissingValueStrategy,
+	pub filled_count: u64,
+	pub dropped_count: u64,
+}
+
+/// Rows of one series in (timestamp, values) pairs, values are in the same order as
+/// [`PreprocessConfig::value_columns`]. NaN marks a missing value which is going to be imputed.
+#[derive(Default, Clone, Debug)]
+pub struct SourceSeries {
+	pub id: Option<Arc<String>>, // None if there is no series column
+	pub points: Vec<(u64, Vec<f64>)>,
+}
+
+pub struct BatchInfo {
+	pub sequence_size: u32,
+	pub sequence_count: u32,
+	pub interval: u32,
+}
+
+/// Re-sampled series, each batch is one sequence of equally spaced steps. Each step holds one value
+/// per column, the predictable column comes first, then the target columns, then the feature
+/// columns. Panel data has one of this per series, all of them share the same interval.
+#[derive(Default, Clone, Debug)]
+pub struct HistoricalData {
+	pub series_id: Option<Arc<String>>,
+	pub batches: Vec<Vec<Vec<f64>>>, // [sequence][step][column]
+	pub first_timestamp: u64,
+	pub timestamp_interval: u32,
+	pub batch_periode: BatchPeriode,
+	pub datetime_column: Arc<String>,
+	pub predictable_column: Arc<String>,
+	pub target_columns: Vec<Arc<String>>,
+	pub feature_columns: Vec<Arc<String>>,
+	pub series_column: Option<Arc<String>>,
+	pub zone: Zone, // Calendar of the batch periode and the predicted timestamps
+}
+
+/// Normalization of each column, in the same order as [`HistoricalData::batches`]
+#[derive(Default, Clone, Debug)]
+pub struct NormalParam {
+	pub mean: Vec<f64>,
+	pub stdev: Vec<f64>,
+}
+
+/// Accuracy of predicted values against actual values, both in original unit. MAPE and sMAPE are
+/// in percent, undefined ones (e.g. MAPE when every actual value is 0) are `None`.
+#[derive(Default, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastMetrics {
+	pub mae: f64,
+	pub rmse: f64,
+	pub mape: Option<f64>,
+	pub smape: Option<f64>,
+	pub r2: Option<f64>,
+}
+
+/// [`ForecastMetrics`] of one forecasted column.
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnMetrics {
+	pub column: Arc<String>,
+	pub metrics: ForecastMetrics,
+}
+
+#[derive(Default, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IterativePoint {
+	pub x: u32,                      // Epoch
+	pub metrics: Vec<ColumnMetrics>, // Measured on validation set, predictable column first
+}
+
+/// Hyperparameters and stop rule of [`super::train::train`], missing fields fall back to default.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrainSettings {
+	pub hidden_size: usize,
+	pub num_layers: usize,
+	pub dropout: f64,
+	pub bidirectional: bool,
+	pub learning_rate: f64,
+	pub batch_size: usize, // Sequences per optimizer step
+	pub max_epochs: u32,
+	pub clip_norm: Option<f32>, // None disables gradient clipping
+	pub patience: u32,          // Stops early after this many epochs without improvement...
+	pub min_delta: f32,         // ...which means validation loss drops by more than this...
+	pub min_epochs: u32,        // ...but not before this epoch
+	pub series_embedding: bool, // Tells the series apart with one-hot input, only for panel data
+}
+
+impl Default for TrainSettings {
+	fn default() -> Self {
+		Self {
+			hidden_size: 128,
+			num_layers: 1,
+			dropout: 0.1,
+			bidirectional: false,
+			learning_rate: 1e-3,
+			batch_size: 32,
+			max_epochs: 500,
+			clip_norm: Some(1.0),
+			patience: 20,
+			min_delta: 0.0,
+			min_epochs: 50,
+			series_embedding: false,
+		}
+	}
+}
+
+/// Why [`super::train::train`] has stopped.
+#[derive(Default, PartialEq, Eq, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum StopReason {
+	#[default]
+	MaxEpochs,
+	NoImprovement, // Patience has run out
+	Canceled,
+}
+
+impl std::fmt::Display for StopReason {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			StopReason::MaxEpochs => write!(f, "maximum epochs reached"),
+			StopReason::NoImprovement => write!(f, "validation loss stopped improving"),
+			StopReason::Canceled => write!(f, "it was canceled"),
+		}
+	}
+}
+
+/// Lets another thread pause, resume, or cancel [`super::train::train`], checked once per epoch.
+#[derive(Default, Debug)]
+pub struct TrainControl {
+	pub(super) canceled: AtomicBool,
+	pub(super) paused: Mutex<bool>,
+	pub(super) resumed: Condvar,
+}
+
+#[derive(burn::config::Config)]
+pub(super) struct TrainingConfig {
+	pub model: LstmNetworkConfig,
+	pub optimizer: AdamConfig,
+
+	#[config(default = 1e-3)]
+	pub lr: f64,
+}
+
+/// One normalized sequence, the model learns to guess `target` which comes right after `input`.
+#[derive(Clone, Debug)]
+pub struct SequenceItem {
+	pub input: Vec<Vec<f64>>, // [step][column]
+	pub target: Vec<f64>,     // One per column
+}
+
+/// Stacked [`SequenceItem`]s, `inputs` is shaped (batch, sequence, columns) and `targets` is
+/// (batch, columns).
+#[derive(Clone, Debug)]
+pub struct SequenceBatch<B: Backend> {
+	pub inputs: Tensor<B, 3>,
+	pub targets: Tensor<B, 2>,
+}
+
+#[derive(Default, Clone, Copy, Debug)]
+pub struct SequenceBatcher;
+
+pub struct TrainInput {
+	pub train_items: Vec<SequenceItem>,
+	pub valid_items: Vec<SequenceItem>,
+	pub mean: Vec<f64>,
+	pub stdev: Vec<f64>,
+	pub series_count: usize, // Width of the one-hot series embedding, 0 if disabled
+	pub predicted_columns: Vec<Arc<String>>, // Leading columns of each step, which are scored
+}
+
+/// Model which is ready to forecast, together with what it needs to denormalize its output and to
+/// re-sample fresh data the same way as it was trained.
+#[derive(Clone, Debug)]
+pub struct TrainedModel {
+	pub network: LstmNetwork<NdArray>,
+	pub network_config: LstmNetworkConfig,
+	pub normal_param: NormalParam,
+	pub metrics: Vec<ColumnMetrics>, // Same order as predicted_columns()
+	pub first_timestamp: u64,
+	pub timestamp_interval: u32,
+	pub sequence_size: u32,
+	pub batch_periode: BatchPeriode,
+	pub datetime_column: Arc<String>,
+	pub predictable_column: Arc<String>,
+	pub target_columns: Vec<Arc<String>>,
+	pub feature_columns: Vec<Arc<String>>,
+	pub series_column: Option<Arc<String>>,
+	pub series_ids: Vec<Arc<String>>, // Order of the one-hot series embedding, empty if disabled
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonPoint {
+	pub x: DateTime<FixedOffset>,
+	pub y0: Option<f64>,
+	pub y1: Option<f64>,
+}
+
+/// Historical (`y0`) and predicted (`y1`) values of one column of one series sharing the same time
+/// axis.
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetForecast {
+	pub series_id: Option<Arc<String>>,
+	pub column: Arc<String>,
+	pub graph: Vec<ComparisonPoint>,
+}
+
+/// One graph per predicted column of each series, the predictable column of each series comes
+/// first.
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+pub struct Forecast {
+	pub targets: Vec<TargetForecast>,
+}
+
+/// How far [`super::forecast::predict`] goes beyond the historical data.
+#[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ForecastHorizon {
+	Steps { count: usize },                       // Re-sampled steps
+	Duration { amount: u32, unit: BatchPeriode }, // e.g. 30 daily means the next 30 days
+}
+
+/// How [`super::forecast::backtest`] hides the historical data.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestSettings {
+	pub holdout: usize, // Hidden steps after each origin
+	pub origins: usize, // Number of rolling origins
+}
+
+impl Default for BacktestSettings {
+	fn default() -> Self {
+		Self {
+			holdout: 10,
+			origins: 1,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestOrigin {
+	pub series_id: Option<Arc<String>>,
+	pub x: DateTime<FixedOffset>, // First hidden timestamp
+	pub metrics: ForecastMetrics,
+}
+
+/// Actual (`y0`) and backtested (`y1`) values of the predictable column, one graph per series.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Backtest {
+	pub settings: BacktestSettings,
+	pub held_out: usize, // Last steps of each series which were never trained on, origins lie within
+	pub metrics: ForecastMetrics, // Over every origin of every series
+	pub origins: Vec<BacktestOrigin>,
+	pub graphs: Vec<TargetForecast>,
+}
+
+/// Historical data of a project read again, see [`super::storage::reopen_source`].
+pub struct ReopenedSource {
+	pub source_data: SourceData,
+	pub content: SheetContent,
+	pub preprocessed_data: Vec<HistoricalData>, // One per series
+}
+
+/// Whole session which can be resumed later, see [`super::storage::save_project`].
+#[derive(Default, Clone, Debug)]
+pub struct Project {
+	pub source_path: PathBuf,
+	pub source_copy: Option<Vec<u8>>, // Embedded content of the file at source_path
+	pub csv_dialect: CsvDialectOverride,
+	pub preprocess_config: Option<PreprocessConfig>,
+	pub train_settings: Option<TrainSettings>,
+	pub trained_model: Option<TrainedModel>,
+	pub forecast: Option<Forecast>,
+	pub backtest: Option<Backtest>,
+}
@@ -16,23 +16,27 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use crate::typedef::ErrorInfo;
+use crate::engine::forecast::{backtest, predict, write_prediction};
+use crate::engine::storage::{self, MODEL_EXTENSION};
+use crate::typedef::{AppProgress, ErrorInfo};
 use std::{borrow::Cow, sync::Mutex};
 use tauri::{AppHandle, Emitter, State};
 use tauri_plugin_dialog::DialogExt;
 use tauri_plugin_dialog::FilePath;
 
-use super::helper::*;
 use super::typedef::*;
 
-const ERROR_SAVE: &'static str = "Cannot Save File";
-
+/// Forecasts beyond the historical data once, then keeps it until another `horizon` is requested.
+/// When `backtest_settings` is set, the trained model is also backtested against the historical
+/// data.
 #[tauri::command]
 pub(crate) async fn get_evaluation(
 	app: AppHandle,
 	state: State<'_, Mutex<crate::typedef::AppState>>,
+	horizon: Option<ForecastHorizon>,
+	backtest_settings: Option<BacktestSettings>,
 ) -> Result<EvaluationReport, ()> {
-	let input = {
+	let (preprocessed_data, trained_model, forecast) = {
 		let mut guarded_state = match state.lock() {
 			Ok(ok) => ok,
 			Err(err) => {
@@ -49,20 +53,40 @@ pub(crate) async fn get_evaluation(
 			}
 		};
 
-		let preprocessed_data = match &guarded_state.preprocessed_data {
-			Some(found) => found.clone(),
-			None => {
-				*guarded_state = Default::default();
-				app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
-					.unwrap();
-				return Err(());
-			}
+		// Another horizon needs another prediction
+		let horizon_changed = horizon.is_some() && horizon != guarded_state.forecast_horizon;
+		let forecast = match horizon_changed {
+			true => None,
+			false => guarded_state.forecast.clone(),
 		};
 
-		guarded_state.preprocessed_data = None;
+		// Restored from a project file, or already predicted before
+		if let (Some(forecast), Some(trained_model), None) =
+			(&forecast, &guarded_state.trained_model, &backtest_settings)
+		{
+			return Ok(build_report(
+				trained_model,
+				forecast.clone(),
+				guarded_state.backtest.clone(),
+			));
+		}
 
-		let trained_model = match &guarded_state.trained_model {
-			Some(found) => found.clone(),
+		// Kept in state, so it can still be backtested afterward
+		let preprocessed_data = match guarded_state.preprocessed_data.clone() {
+			Some(found) => found,
+			None if guarded_state.trained_model.is_some() => {
+				app.emit::<ErrorInfo>(
+					crate::event::DIALOG_ERROR,
+					ErrorInfo {
+						title: Cow::Borrowed("Cannot Evaluate Again"),
+						message: String::from(
+							"Because the historical data has not been re-sampled yet, preprocess it again first",
+						),
+					},
+				)
+				.unwrap();
+				return Err(());
+			}
 			None => {
 				*guarded_state = Default::default();
 				app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
@@ -71,9 +95,8 @@ pub(crate) async fn get_evaluation(
 			}
 		};
 
-		guarded_state.trained_model = None;
-
-		let normal_param = match &guarded_state.normal_param {
+		// Keep the model in state, so it can still be saved afterward
+		let trained_model = match &guarded_state.trained_model {
 			Some(found) => found.clone(),
 			None => {
 				*guarded_state = Default::default();
@@ -83,26 +106,31 @@ pub(crate) async fn get_evaluation(
 			}
 		};
 
-		let last_confidence = guarded_state
-			.train_progress
-			.confidence_points
-			.last()
-			.and_then(|found| Some(found.y))
-			.unwrap_or_default();
-
-		PredictionInput {
-			preprocessed_data,
-			trained_model,
-			normal_param,
-			last_confidence,
-		}
+		(preprocessed_data, trained_model, forecast)
 	};
 
-	let confidence = *(&input.last_confidence);
-
-	let cloned_app = app.clone();
-	let graph =
-		match tauri::async_runtime::spawn_blocking(move || predict(&input, &cloned_app)).await {
+	let mut progress = AppProgress { app: app.clone() };
+	let (trained_model, forecast_result, backtest_result) =
+		match tauri::async_runtime::spawn_blocking(move || {
+			let forecast_result = match forecast {
+				Some(found) => Ok(found),
+				None => predict(
+					&trained_model,
+					&preprocessed_data,
+					horizon.as_ref(),
+					&mut progress,
+				),
+			};
+			let backtest_result = backtest_settings
+				.map(|settings| {
+					backtest(&trained_model, &preprocessed_data, &settings, &mut progress)
+				})
+				.transpose();
+
+			(trained_model, forecast_result, backtest_result)
+		})
+		.await
+		{
 			Ok(ok) => ok,
 			Err(err) => {
 				app.emit::<ErrorInfo>(
@@ -118,30 +146,24 @@ pub(crate) async fn get_evaluation(
 			}
 		};
 
-	let [high_peak, low_peak] = graph.iter().filter(|each| each.y1.is_some()).fold(
-		[Option::<&ComparisonPoint>::None; 2],
-		|[last_max, last_min], each| {
-			let y1 = each.y1.unwrap();
-			let last_max_y1 = last_max.and_then(|found| found.y1).unwrap_or(f64::MIN);
-			let last_min_y1 = last_min.and_then(|found| found.y1).unwrap_or(f64::MAX);
-
-			let max = Some(if y1 > last_max_y1 {
-				each
-			} else {
-				last_max.unwrap_or(each)
-			});
-
-			let min = Some(if y1 < last_min_y1 {
-				each
-			} else {
-				last_min.unwrap_or(each)
-			});
-
-			[max, min]
-		},
-	);
+	let forecast = match forecast_result {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
+			return Err(());
+		}
+	};
 
-	let [high_peak, low_peak] = [high_peak.cloned(), low_peak.cloned()];
+	// Failed backtest does not affect the forecast, keep going without it
+	let new_backtest = match backtest_result {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
+			None
+		}
+	};
 
 	let mut guarded_state = match state.lock() {
 		Ok(ok) => ok,
@@ -159,26 +181,19 @@ pub(crate) async fn get_evaluation(
 		}
 	};
 
-	// Free-up the memory
-	guarded_state.trained_model = None;
-	guarded_state.predicted_data = None;
-
-	guarded_state.predicted_data = Some(
-		graph
-			.iter()
-			.filter_map(|each| match each.y1 {
-				Some(y1) => Some((each.x, y1)),
-				None => None,
-			})
-			.collect::<Vec<_>>(),
-	);
-
-	Ok(EvaluationReport {
-		confidence,
-		graph,
-		high_peak,
-		low_peak,
-	})
+	guarded_state.forecast = Some(forecast.clone());
+	if horizon.is_some() {
+		guarded_state.forecast_horizon = horizon;
+	}
+	if new_backtest.is_some() {
+		guarded_state.backtest = new_backtest;
+	}
+
+	Ok(build_report(
+		&trained_model,
+		forecast,
+		guarded_state.backtest.clone(),
+	))
 }
 
 #[tauri::command]
@@ -221,13 +236,14 @@ pub(crate) async fn save_prediction(
 		}
 	};
 
-	let mut writer = match csv::Writer::from_path(source_path) {
+	let guarded_state = match state.lock() {
 		Ok(ok) => ok,
 		Err(err) => {
+			app.emit(crate::event::CORE_PANIC, ()).unwrap();
 			app.emit::<ErrorInfo>(
 				crate::event::DIALOG_ERROR,
 				ErrorInfo {
-					title: Cow::Borrowed(ERROR_SAVE),
+					title: Cow::Borrowed("State Inaccessible before Saving"),
 					message: err.to_string(),
 				},
 			)
@@ -236,27 +252,14 @@ pub(crate) async fn save_prediction(
 		}
 	};
 
-	if let Err(err) = writer.write_record(["Date/Time", "Predicted Value"]) {
-		app.emit::<ErrorInfo>(
-			crate::event::DIALOG_ERROR,
-			ErrorInfo {
-				title: Cow::Borrowed(ERROR_SAVE),
-				message: err.to_string(),
-			},
-		)
-		.unwrap();
-		return Err(());
-	}
-
-	let guarded_state = match state.lock() {
-		Ok(ok) => ok,
-		Err(err) => {
-			app.emit(crate::event::CORE_PANIC, ()).unwrap();
+	let forecast = match &guarded_state.forecast {
+		Some(found) => found,
+		None => {
 			app.emit::<ErrorInfo>(
 				crate::event::DIALOG_ERROR,
 				ErrorInfo {
-					title: Cow::Borrowed("State Inaccessible before Saving"),
-					message: err.to_string(),
+					title: Cow::Borrowed("Cannot Save File"),
+					message: String::from("The predicted data does not exist in memory"),
 				},
 			)
 			.unwrap();
@@ -264,48 +267,139 @@ pub(crate) async fn save_prediction(
 		}
 	};
 
-	let predicted_data = match &guarded_state.predicted_data {
-		Some(found) => found,
-		None => {
+	if let Err(err) = write_prediction(&source_path, forecast, &guarded_state.column_units) {
+		app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+			.unwrap();
+		return Err(());
+	}
+
+	Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn save_model(
+	app: AppHandle,
+	state: State<'_, Mutex<crate::typedef::AppState>>,
+) -> Result<(), ()> {
+	let (tx, mut rx) = tauri::async_runtime::channel::<Option<FilePath>>(1024);
+
+	app.dialog()
+		.file()
+		.set_title("Save the trained model")
+		.add_filter("Project::Tarot Model File", &[MODEL_EXTENSION])
+		.save_file(move |file_path| {
+			let _ = tx.blocking_send(file_path);
+		});
+
+	let file_path_option = rx.recv().await;
+	if file_path_option
+		.as_ref()
+		.is_none_or(|found| found.is_none())
+	{
+		return Ok(());
+	}
+
+	let file_path = file_path_option.unwrap().unwrap();
+	let target_path = match file_path.into_path() {
+		Ok(ok) => ok.with_extension(MODEL_EXTENSION),
+		Err(err) => {
 			app.emit::<ErrorInfo>(
 				crate::event::DIALOG_ERROR,
 				ErrorInfo {
-					title: Cow::Borrowed(ERROR_SAVE),
-					message: String::from("The predicted data does not exist in memory"),
+					title: Cow::Borrowed("Cannot Parse File Path"),
+					message: err.to_string(),
 				},
 			)
 			.unwrap();
-			return Err(());
+			return Ok(());
 		}
 	};
 
-	for each in predicted_data {
-		let datetime = each.0.to_rfc3339();
-		let value = each.1.to_string();
-		if let Err(err) = writer.write_record([datetime, value]) {
+	let trained_model = {
+		let guarded_state = match state.lock() {
+			Ok(ok) => ok,
+			Err(err) => {
+				app.emit(crate::event::CORE_PANIC, ()).unwrap();
+				app.emit::<ErrorInfo>(
+					crate::event::DIALOG_ERROR,
+					ErrorInfo {
+						title: Cow::Borrowed("State Inaccessible before Saving Model"),
+						message: err.to_string(),
+					},
+				)
+				.unwrap();
+				return Err(());
+			}
+		};
+
+		match &guarded_state.trained_model {
+			Some(found) => found.clone(),
+			None => {
+				app.emit::<ErrorInfo>(
+					crate::event::DIALOG_ERROR,
+					ErrorInfo {
+						title: Cow::Borrowed("Cannot Save Model"),
+						message: String::from("The trained model does not exist in memory"),
+					},
+				)
+				.unwrap();
+				return Err(());
+			}
+		}
+	};
+
+	let saved_result = match tauri::async_runtime::spawn_blocking(move || {
+		storage::save_model(&trained_model, &target_path)
+	})
+	.await
+	{
+		Ok(ok) => ok,
+		Err(err) => {
 			app.emit::<ErrorInfo>(
 				crate::event::DIALOG_ERROR,
 				ErrorInfo {
-					title: Cow::Borrowed(ERROR_SAVE),
+					title: Cow::Borrowed("Cannot Create New Process Thread"),
 					message: err.to_string(),
 				},
 			)
 			.unwrap();
 			return Err(());
 		}
-	}
+	};
 
-	if let Err(err) = writer.flush() {
-		app.emit::<ErrorInfo>(
-			crate::event::DIALOG_ERROR,
-			ErrorInfo {
-				title: Cow::Borrowed("Potentially Corrupted"),
-				message: err.to_string(),
-			},
-		)
-		.unwrap();
+	if let Err(err) = saved_result {
+		app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+			.unwrap();
 		return Err(());
 	}
 
 	Ok(())
 }
+
+fn build_report(
+	trained_model: &TrainedModel,
+	forecast: Forecast,
+	backtest: Option<Backtest>,
+) -> EvaluationReport {
+	let targets = forecast
+		.targets
+		.into_iter()
+		.map(|each| {
+			let [high_peak, low_peak] = each.peaks();
+
+			TargetReport {
+				series_id: each.series_id,
+				column: each.column,
+				graph: each.graph,
+				high_peak,
+				low_peak,
+			}
+		})
+		.collect::<Vec<_>>();
+
+	EvaluationReport {
+		backtest,
+		metrics: trained_model.metrics.clone(),
+		targets,
+	}
+}
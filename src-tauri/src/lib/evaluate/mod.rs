@@ -18,5 +18,4 @@
 
 pub(super) mod command;
 pub(super) mod event;
-pub(super) mod helper;
 pub(super) mod typedef;
@@ -16,32 +16,28 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use burn::backend::NdArray;
-use chrono::{DateTime, Local};
 use serde::Serialize;
+use std::sync::Arc;
 
-use crate::train::model::LstmNetwork;
+pub(crate) use crate::engine::typedef::{
+	Backtest, BacktestSettings, ColumnMetrics, ComparisonPoint, Forecast, ForecastHorizon,
+	TrainedModel,
+};
 
 #[derive(Default, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct ComparisonPoint {
-	pub x: DateTime<Local>,
-	pub y0: Option<f64>,
-	pub y1: Option<f64>,
-}
-
-#[derive(Default, Serialize, Clone)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct EvaluationReport {
-	pub confidence: f32,
+pub(crate) struct TargetReport {
+	pub series_id: Option<Arc<String>>,
+	pub column: Arc<String>,
 	pub graph: Vec<ComparisonPoint>,
 	pub high_peak: Option<ComparisonPoint>,
 	pub low_peak: Option<ComparisonPoint>,
 }
 
-pub(super) struct PredictionInput {
-	pub preprocessed_data: crate::typedef::HistoricalData,
-	pub trained_model: LstmNetwork<NdArray>,
-	pub normal_param: crate::typedef::NormalParam,
-	pub last_confidence: f32,
+#[derive(Default, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EvaluationReport {
+	pub metrics: Vec<ColumnMetrics>, // Measured on validation set while training
+	pub targets: Vec<TargetReport>,  // Predictable column of each series comes first
+	pub backtest: Option<Backtest>,
 }
@@ -26,15 +26,20 @@ use typedef::*;
 
 mod event;
 
+pub mod engine;
+
+mod cli;
 mod data;
 mod evaluate;
 mod preprocess;
+mod project;
 mod train;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 use data::command::*;
 use evaluate::command::*;
 use preprocess::command::*;
+use project::command::*;
 use train::command::*;
 
 #[tauri::command]
@@ -45,11 +50,19 @@ fn get_page_index(state: State<'_, Mutex<AppState>>) -> u8 {
 #[tauri::command]
 fn restart(app: AppHandle, state: State<'_, Mutex<crate::typedef::AppState>>) {
 	let mut guarded_state = state.lock().unwrap();
+	if let Some(control) = &guarded_state.train_control {
+		control.cancel();
+	}
 	*guarded_state = Default::default();
 	app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
 		.unwrap();
 }
 
+/// Entry point of `tarot-cli`, the headless variant of this app
+pub fn run_cli() -> std::process::ExitCode {
+	cli::command::run()
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
 	tauri::Builder::default()
@@ -60,11 +73,20 @@ pub fn run() {
 			load_data,
 			get_data_info,
 			select_sheet,
+			override_column_type,
+			set_missing_value_strategy,
 			submit_preprocess_config,
 			start_train,
 			get_train_progress,
+			cancel_train,
+			pause_train,
+			resume_train,
 			get_evaluation,
 			save_prediction,
+			load_model,
+			save_model,
+			save_project,
+			open_project,
 			restart
 		])
 		.setup(|app| {
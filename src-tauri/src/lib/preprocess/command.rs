@@ -16,34 +16,29 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use calamine::{DataType, Reader};
-use chrono::Local;
-use csv::Position;
-use parse_datetime::parse_datetime;
 use std::{
 	borrow::Cow,
-	collections::HashMap,
-	hash::RandomState,
 	sync::{Arc, Mutex},
 };
 use tauri::{AppHandle, Emitter, State};
 
-use super::helper::*;
 use super::typedef::*;
-use crate::typedef::{AppState, CellValue, ErrorInfo, HistoricalData, SourceData};
-
-const ERROR_EXTENSION: &'static str = "File Type Unsupported";
-const ERROR_HEADER: &'static str = "Cannot Read Header";
-const ERROR_CONTENT: &'static str = "Failed to Read Content";
-const ERROR_INCOMPLETE: &'static str = "Data is Incomplete";
-const ERROR_INCOMPLETE_SUFFIX: &'static str = "this CSV file";
-const ERROR_INCONSISTENT: &'static str = "Inconsistent Data Type";
-const ERROR_MODIFIED: &'static str = "Selected Data just Modified";
-const ERROR_RESET: &'static str = "Cannot Re-read the Data";
+use crate::engine::preprocess::*;
+use crate::engine::source::*;
+use crate::typedef::{
+	AppState, CsvDialectOverride, DatetimeFormat, ErrorInfo, MissingValueStrategy, SheetLayout,
+	SourceData,
+};
 
+/// Reopens the loaded file, `csv_dialect` replaces the previous one when user changes how CSV is
+/// read.
 #[tauri::command]
-pub(crate) fn get_data_info(app: AppHandle, state: State<'_, Mutex<AppState>>) -> DataInfo {
-	let source_path = {
+pub(crate) fn get_data_info(
+	app: AppHandle,
+	state: State<'_, Mutex<AppState>>,
+	csv_dialect: Option<CsvDialectOverride>,
+) -> DataInfo {
+	let (source_path, csv_dialect) = {
 		let mut guarded_state = match state.lock() {
 			Ok(ok) => ok,
 			Err(err) => {
@@ -68,90 +63,22 @@ pub(crate) fn get_data_info(app: AppHandle, state: State<'_, Mutex<AppState>>) -
 			return Default::default();
 		}
 
-		guarded_state.source_path.clone().unwrap()
-	};
-
-	let extension_option = source_path.extension();
-	if extension_option.is_none() {
-		app.emit::<ErrorInfo>(
-			crate::event::DIALOG_ERROR,
-			ErrorInfo {
-				title: Cow::Borrowed(ERROR_EXTENSION),
-				message: format!(
-					"Because \"{}\" file has no extension",
-					source_path.to_string_lossy().into_owned()
-				),
-			},
-		)
-		.unwrap();
-
-		crate::restart(app, state);
-		return Default::default();
-	}
+		if let Some(found) = csv_dialect {
+			guarded_state.csv_dialect = found;
+		}
 
-	let extension_str_option = extension_option.unwrap().to_str();
-	if extension_str_option.is_none() {
-		app.emit::<ErrorInfo>(
-			crate::event::DIALOG_ERROR,
-			ErrorInfo {
-				title: Cow::Borrowed(ERROR_EXTENSION),
-				message: String::from("Cannot transform file extension encoding"),
-			},
+		(
+			guarded_state.source_path.clone().unwrap(),
+			guarded_state.csv_dialect.clone(),
 		)
-		.unwrap();
-
-		crate::restart(app, state);
-		return Default::default();
-	}
-
-	let extension = extension_str_option.unwrap().to_lowercase();
-
-	let source_data = match extension.as_str() {
-		"csv" => match csv::Reader::from_path(&source_path) {
-			Ok(csv_file) => SourceData::Csv(csv_file),
-			Err(err) => {
-				app.emit::<ErrorInfo>(
-					crate::event::DIALOG_ERROR,
-					ErrorInfo {
-						title: Cow::Borrowed("Failed to Read CSV File"),
-						message: err.to_string(),
-					},
-				)
-				.unwrap();
+	};
 
-				crate::restart(app, state);
-				return Default::default();
-			}
-		},
-		"xlsx" | "xls" | "xlsb" | "ods" => match calamine::open_workbook_auto(&source_path) {
-			Ok(sheet_file) => SourceData::Spreadsheet(sheet_file),
-			Err(err) => {
-				app.emit::<ErrorInfo>(
-					crate::event::DIALOG_ERROR,
-					ErrorInfo {
-						title: Cow::Owned(format!(
-							"Failed to Read {} File",
-							extension.to_uppercase()
-						)),
-						message: err.to_string(),
-					},
-				)
+	let source_data = match open_source_data(&source_path, &csv_dialect) {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
 				.unwrap();
 
-				crate::restart(app, state);
-				return Default::default();
-			}
-		},
-		_ => {
-			app.emit::<ErrorInfo>(
-				crate::event::DIALOG_ERROR,
-				ErrorInfo {
-					title: Cow::Borrowed(ERROR_EXTENSION),
-					message: format!("Cannot parse \"{}\" file extension", extension),
-				},
-			)
-			.unwrap();
-
 			crate::restart(app, state);
 			return Default::default();
 		}
@@ -162,13 +89,23 @@ pub(crate) fn get_data_info(app: AppHandle, state: State<'_, Mutex<AppState>>) -
 		.and_then(|name| Some(name.to_str()?.to_string()))
 		.unwrap_or(String::from("(unknown)"));
 
-	let tabs = match &source_data {
-		SourceData::None => unreachable!(),
-		SourceData::Csv(_reader) => None,
-		SourceData::Spreadsheet(sheets) => Some(sheets.sheet_names()),
+	let tabs = match list_tabs(&source_data) {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
+
+			crate::restart(app, state);
+			return Default::default();
+		}
+	};
+	let is_database = matches!(source_data, SourceData::Sqlite(_));
+	let csv_dialect = match &source_data {
+		SourceData::Csv(source) => Some(source.dialect.clone()),
+		_ => None,
 	};
 
-	{
+	let (submitted_tab, train_settings) = {
 		let mut guarded_state = match state.lock() {
 			Ok(ok) => ok,
 			Err(err) => {
@@ -186,27 +123,108 @@ pub(crate) fn get_data_info(app: AppHandle, state: State<'_, Mutex<AppState>>) -
 		};
 
 		guarded_state.source_data = source_data;
+
+		(
+			guarded_state
+				.preprocess_config
+				.as_ref()
+				.and_then(|config| config.tab_name.clone()),
+			guarded_state.train_settings.clone().unwrap_or_default(),
+		)
 	};
 
+	// Reopen the sheet (or custom query) which was submitted before, e.g. from a project file
 	let selected_tab = tabs
 		.as_ref()
-		.and_then(|tabs| tabs.iter().nth(0).cloned())
+		.and_then(|tabs| match submitted_tab {
+			Some(found) if is_database || tabs.contains(&found) => Some(found),
+			_ => tabs.iter().nth(0).cloned(),
+		})
 		.unwrap_or(String::new());
 
-	let sheet_info = select_sheet(app, state, selected_tab);
+	let sheet_info = select_sheet(app, state, selected_tab, None, None);
 
 	DataInfo {
 		name,
 		tabs,
+		is_database,
+		csv_dialect,
 		sheet_info,
+		train_settings,
 	}
 }
 
+/// Reads the sheet, `datetime_format` replaces the previous one when user changes how the date/time
+/// column is read. Same for `sheet_layout` when user moves the header of a worksheet.
 #[tauri::command]
 pub(crate) fn select_sheet(
 	app: AppHandle,
 	state: State<'_, Mutex<AppState>>,
 	tab_name: String,
+	datetime_format: Option<DatetimeFormat>,
+	sheet_layout: Option<SheetLayout>,
+) -> SheetInfo {
+	read_selected_sheet(
+		app,
+		state,
+		tab_name,
+		datetime_format,
+		None,
+		sheet_layout,
+		None,
+	)
+}
+
+/// Re-reads the sheet with the type of `field` chosen by user, or decided by majority again if
+/// `column_type` is unset.
+#[tauri::command]
+pub(crate) fn override_column_type(
+	app: AppHandle,
+	state: State<'_, Mutex<AppState>>,
+	tab_name: String,
+	field: Arc<String>,
+	column_type: Option<ColumnType>,
+) -> SheetInfo {
+	read_selected_sheet(
+		app,
+		state,
+		tab_name,
+		None,
+		Some((field, column_type)),
+		None,
+		None,
+	)
+}
+
+/// Re-reads the sheet with missing or invalid cells of `field` imputed by `strategy`, or dropping
+/// their rows again if `strategy` is unset.
+#[tauri::command]
+pub(crate) fn set_missing_value_strategy(
+	app: AppHandle,
+	state: State<'_, Mutex<AppState>>,
+	tab_name: String,
+	field: Arc<String>,
+	strategy: Option<MissingValueStrategy>,
+) -> SheetInfo {
+	read_selected_sheet(
+		app,
+		state,
+		tab_name,
+		None,
+		None,
+		None,
+		Some((field, strategy)),
+	)
+}
+
+fn read_selected_sheet(
+	app: AppHandle,
+	state: State<'_, Mutex<AppState>>,
+	tab_name: String,
+	datetime_format: Option<DatetimeFormat>,
+	column_type_override: Option<(Arc<String>, Option<ColumnType>)>,
+	sheet_layout: Option<SheetLayout>,
+	missing_value_override: Option<(Arc<String>, Option<MissingValueStrategy>)>,
 ) -> SheetInfo {
 	let mut guarded_state = match state.lock() {
 		Ok(ok) => ok,
@@ -226,279 +244,92 @@ pub(crate) fn select_sheet(
 
 	guarded_state.column_lookup = None;
 
-	if let SourceData::None = &guarded_state.source_data {
-		app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
-			.unwrap();
-		return Default::default();
-	}
-
-	// CSV will always ignore this
-	let selected_sheet = match &mut guarded_state.source_data {
-		SourceData::Spreadsheet(sheets) => {
-			let sheet = match sheets.worksheet_range(&tab_name) {
-				Ok(ok) => ok,
-				Err(err) => {
-					app.emit::<ErrorInfo>(
-						crate::event::DIALOG_ERROR,
-						ErrorInfo {
-							title: Cow::Borrowed("Cannot Read Worksheet"),
-							message: err.to_string(),
-						},
-					)
-					.unwrap();
-
-					return SheetInfo {
-						tab_name: Some(tab_name),
-						..Default::default()
-					};
-				}
-			};
-
-			Some(sheet)
+	let is_spreadsheet = match &guarded_state.source_data {
+		SourceData::None => {
+			app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
+				.unwrap();
+			return Default::default();
 		}
-		_ => None,
+		SourceData::Spreadsheet(_) | SourceData::Sqlite(_) => true,
+		_ => false,
 	};
 
-	let column_names = match &mut guarded_state.source_data {
-		SourceData::None => unreachable!(),
-		SourceData::Csv(reader) => {
-			let headers = match reader.headers() {
-				Ok(ok) => ok,
-				Err(err) => {
-					app.emit::<ErrorInfo>(
-						crate::event::DIALOG_ERROR,
-						ErrorInfo {
-							title: Cow::Borrowed(ERROR_HEADER),
-							message: err.to_string(),
-						},
-					)
-					.unwrap();
-
-					*guarded_state = Default::default();
-					app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
-						.unwrap();
-
-					return Default::default();
-				}
-			};
+	let previous_datetime_format = guarded_state.datetime_format.clone();
+	if let Some(found) = datetime_format {
+		guarded_state.datetime_format = found;
+	}
 
-			headers
-				.iter()
-				.map(|each| each.to_string())
-				.collect::<Vec<_>>()
+	let previous_column_types = guarded_state.column_types.clone();
+	match column_type_override {
+		Some((field, Some(column_type))) => {
+			guarded_state.column_types.insert(field, column_type);
 		}
-		SourceData::Spreadsheet(_) => {
-			let selected_sheet = selected_sheet.as_ref().unwrap();
-			match selected_sheet.headers() {
-				Some(found) => found,
-				None => {
-					app.emit::<ErrorInfo>(
-						crate::event::DIALOG_ERROR,
-						ErrorInfo {
-							title: Cow::Borrowed(ERROR_HEADER),
-							message: String::from("The selected sheet has no header"),
-						},
-					)
-					.unwrap();
-
-					return SheetInfo {
-						tab_name: Some(tab_name),
-						..Default::default()
-					};
-				}
-			}
+		Some((field, None)) => {
+			guarded_state.column_types.remove(&field);
 		}
-	};
-
-	let cells = match &mut guarded_state.source_data {
-		SourceData::None => unreachable!(),
-		SourceData::Csv(reader) => {
-			let results = reader
-				.records()
-				.map(|each| {
-					let row = each.or_else(|err| Err(err.to_string()))?;
-					Ok(row.iter().map(parse_cell).collect::<Vec<_>>())
-				})
-				.collect::<Vec<Result<Vec<CellValue>, String>>>();
-
-			let error = results.iter().find_map(|each| each.as_ref().err()).cloned();
-			if let Some(message) = error {
-				app.emit::<ErrorInfo>(
-					crate::event::DIALOG_ERROR,
-					ErrorInfo {
-						title: Cow::Borrowed(ERROR_CONTENT),
-						message,
-					},
-				)
-				.unwrap();
+		None => (),
+	}
 
-				return SheetInfo {
-					tab_name: Some(tab_name),
-					..Default::default()
-				};
-			}
+	let previous_sheet_layout = guarded_state.sheet_layout.clone();
+	if let Some(found) = sheet_layout {
+		guarded_state.sheet_layout = found;
+	}
 
-			results
-				.iter()
-				.filter_map(|each| each.as_ref().ok())
-				.cloned()
-				.collect::<Vec<_>>()
+	let previous_missing_values = guarded_state.missing_values.clone();
+	match missing_value_override {
+		Some((field, Some(strategy))) => {
+			guarded_state.missing_values.insert(field, strategy);
 		}
-		SourceData::Spreadsheet(_) => {
-			let sheet = selected_sheet.as_ref().unwrap();
-			sheet
-				.rows()
-				.skip(1) // Skip header row
-				.map(|each| {
-					each.iter()
-						.map(|each| {
-							if let Some(number_value) = each.as_f64() {
-								CellValue::Number(number_value)
-							} else if let Some(datetime_value) = each.as_datetime() {
-								unwrap_timezone_assumption(
-									datetime_value.and_local_timezone(Local),
-									each,
-								)
-							} else if let Some(date_value) = each.as_date() {
-								unwrap_timezone_assumption(
-									date_value
-										.and_time(Local::now().time())
-										.and_local_timezone(Local),
-									each,
-								)
-							} else if let Some(time_value) = each.as_time() {
-								unwrap_timezone_assumption(
-									Local::now()
-										.date_naive()
-										.and_time(time_value)
-										.and_local_timezone(Local),
-									each,
-								)
-							} else {
-								let string_value = each.to_string();
-								parse_cell(string_value.as_str())
-							}
-						})
-						.collect::<Vec<_>>()
-				})
-				.collect::<Vec<_>>()
+		Some((field, None)) => {
+			guarded_state.missing_values.remove(&field);
 		}
-	};
-
-	// Count how many data types (string, number, ...) occurence each column
-	let column_count = column_names.len();
-	let column_type_counters = cells.iter().fold(
-		std::iter::repeat_n(ColumnCounter::default(), column_count).collect::<Vec<_>>(),
-		|mut counters, each_row| {
-			for i in 0..column_count {
-				let counter = counters.get_mut(i).unwrap();
-				let each_cell = match each_row.get(i) {
-					Some(found) => found,
-					None => continue,
-				};
-
-				match each_cell {
-					CellValue::String(_) => counter.string += 1,
-					CellValue::Number(_) => counter.number += 1,
-					CellValue::RowID(_) => {
-						unreachable!()
-					}
-					CellValue::DateTime(_) => counter.datetime += 1,
-					CellValue::Boolean(_) => counter.boolean += 1,
-				}
-			}
+		None => (),
+	}
 
-			counters
-		},
+	let datetime_format = guarded_state.datetime_format.clone();
+	let column_types = guarded_state.column_types.clone();
+	let sheet_layout = guarded_state.sheet_layout.clone();
+	let missing_values = guarded_state.missing_values.clone();
+	let mut content_result = read_sheet(
+		&mut guarded_state.source_data,
+		&tab_name,
+		&datetime_format,
+		&column_types,
+		&sheet_layout,
+		&missing_values,
 	);
 
-	// Pick the most occuring data type for each column
-	let column_types = column_type_counters
-		.iter()
-		.map(|each| {
-			let mut winner = ColumnType::STRING;
-			if each.string < each.number {
-				winner = ColumnType::NUMBER
-			}
-			if each.number < each.datetime {
-				winner = ColumnType::DATETIME
-			}
-			if each.datetime < each.boolean {
-				winner = ColumnType::BOOLEAN
-			}
-			winner
-		})
-		.collect::<Vec<_>>();
-
-	let columns = column_names
-		.iter()
-		.zip(&column_types)
-		.map(|each| ColumnInfo {
-			field: Arc::new(each.0.to_lowercase()),
-			header_name: each.0.clone(),
-			column_type: each.1.clone(),
-		})
-		.collect::<Vec<_>>();
-
-	let selected_datetime_column = match columns
-		.iter()
-		.find(|each| each.column_type == ColumnType::DATETIME)
-	{
-		Some(found) => found.field.clone(),
-		None => {
-			app.emit::<ErrorInfo>(
-				crate::event::DIALOG_ERROR,
-				ErrorInfo {
-					title: Cow::Borrowed(ERROR_INCOMPLETE),
-					message: format!(
-						"There is no Date/Time column in {}",
-						match &selected_sheet {
-							Some(_) => &tab_name,
-							None => ERROR_INCOMPLETE_SUFFIX,
-						}
-					),
-				},
-			)
+	// Choice which does not fit goes back to the previous one, instead of leaving the page
+	let is_choice_changed = datetime_format != previous_datetime_format
+		|| column_types != previous_column_types
+		|| sheet_layout != previous_sheet_layout
+		|| missing_values != previous_missing_values;
+	if content_result.is_err() && is_choice_changed {
+		app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, content_result.err().unwrap())
 			.unwrap();
 
-			// If CSV file
-			if selected_sheet.is_none() {
-				*guarded_state = Default::default();
-				app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
-					.unwrap();
-				return Default::default();
-			}
-
-			return SheetInfo {
-				tab_name: Some(tab_name),
-				..Default::default()
-			};
-		}
-	};
+		guarded_state.datetime_format = previous_datetime_format.clone();
+		guarded_state.column_types = previous_column_types.clone();
+		guarded_state.sheet_layout = previous_sheet_layout.clone();
+		guarded_state.missing_values = previous_missing_values.clone();
+		content_result = read_sheet(
+			&mut guarded_state.source_data,
+			&tab_name,
+			&previous_datetime_format,
+			&previous_column_types,
+			&previous_sheet_layout,
+			&previous_missing_values,
+		);
+	}
 
-	let selected_predictable_column = match columns
-		.iter()
-		.find(|each| each.column_type == ColumnType::NUMBER)
-	{
-		Some(found) => found.field.clone(),
-		None => {
-			app.emit::<ErrorInfo>(
-				crate::event::DIALOG_ERROR,
-				ErrorInfo {
-					title: Cow::Borrowed(ERROR_INCOMPLETE),
-					message: format!(
-						"There is no predictable column in {}",
-						match &selected_sheet {
-							Some(_) => &tab_name,
-							None => ERROR_INCOMPLETE_SUFFIX,
-						}
-					),
-				},
-			)
-			.unwrap();
+	let content = match content_result {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
 
-			// If CSV file
-			if selected_sheet.is_none() {
+			// CSV file has no other sheet to choose
+			if !is_spreadsheet {
 				*guarded_state = Default::default();
 				app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
 					.unwrap();
@@ -512,134 +343,123 @@ pub(crate) fn select_sheet(
 		}
 	};
 
-	let id_field = Arc::new(String::from("id"));
-
-	let row_count = cells.len();
-	let mut dropped_row_indices = Vec::<u64>::new();
-	let rows = cells
-		.iter()
-		.zip(0..row_count)
-		.filter(|(each_row, row_index)| {
-			let is_row_ok = (0..column_count).all(|i| {
-				let each_cell = match each_row.get(i) {
-					Some(found) => found,
-					None => return false,
-				};
-
-				let expected = column_types.get(i).unwrap().clone();
-				let actual = match each_cell {
-					CellValue::String(_) => ColumnType::STRING,
-					CellValue::Number(_) => ColumnType::NUMBER,
-					CellValue::RowID(_) => return false,
-					CellValue::DateTime(_) => ColumnType::DATETIME,
-					CellValue::Boolean(_) => ColumnType::BOOLEAN,
-				};
-
-				expected == actual
-			});
-
-			if !is_row_ok {
-				dropped_row_indices.push(*row_index as u64);
-			}
-
-			is_row_ok
-		})
-		.map(|(each_row, id)| {
-			let pairs_iter = columns
-				.iter()
-				.zip(each_row)
-				.map(|(each_column, each_cell)| (each_column.field.clone(), each_cell.clone()));
-
-			let pairs_iter_with_id = [(id_field.clone(), CellValue::RowID(id as u32))]
-				.into_iter()
-				.chain(pairs_iter);
-
-			HashMap::<_, _, RandomState>::from_iter(pairs_iter_with_id)
-		})
-		.collect::<Vec<_>>();
-
-	let filtered_row_count = rows.len();
-	let source_row_count = cells.len();
-	if filtered_row_count != source_row_count {
-		if filtered_row_count < 10 {
-			app.emit(
-				crate::event::DIALOG_ERROR,
-				ErrorInfo {
-					title: Cow::Borrowed(ERROR_INCONSISTENT),
-					message: format!(
-					"Only {} rows are unusable after data cleaning, left only {} rows which is too few",
-					source_row_count - filtered_row_count,
-					filtered_row_count
-				),
-				},
-			)
+	if let Some(warning) = content.warning {
+		app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, warning)
 			.unwrap();
-
-			return SheetInfo {
-				tab_name: Some(tab_name),
-				..Default::default()
-			};
-		} else {
-			app.emit(crate::event::DIALOG_ERROR, ErrorInfo {
-				title: Cow::Borrowed(ERROR_INCONSISTENT),
-				message: format!(
-					"Data cleaning was automatically done and there are {} rows dropped because of inconsistent cell data type",
-					source_row_count - filtered_row_count
-				)
-			}).unwrap();
-		}
-	}
-
-	if filtered_row_count < 10 {
-		app.emit(
-			crate::event::DIALOG_ERROR,
-			ErrorInfo {
-				title: Cow::Borrowed("Not Enough Rows"),
-				message: format!(
-					"You selected only {} rows, we need more than (or equal) 10 rows",
-					source_row_count - filtered_row_count
-				),
-			},
-		)
-		.unwrap();
-
-		return SheetInfo {
-			tab_name: Some(tab_name),
-			..Default::default()
-		};
 	}
 
-	let allowed_batch_periodes = decide_allowed_batch_periodes(&columns, &rows);
-	let selected_batch_periode = allowed_batch_periodes
+	let allowed_batch_periodes = decide_allowed_batch_periodes(&content.columns, &content.rows);
+	let mut selected_batch_periode = allowed_batch_periodes
 		.get(1)
 		.or(allowed_batch_periodes.get(0))
 		.cloned()
 		.unwrap_or(BatchPeriode::YEARLY);
 
-	let row_selection = RowSelection {
+	let mut selected_datetime_column = content.selected_datetime_column;
+	let mut selected_predictable_column = content.selected_predictable_column;
+	let mut selected_target_columns = Vec::<Arc<String>>::new();
+	let mut selected_feature_columns = Vec::<Arc<String>>::new();
+	let mut selected_series_column = Option::<Arc<String>>::None;
+	let mut row_selection = RowSelection {
 		ids: Vec::new(),
 		selection_type: SelectionType::EXCLUDE,
 	};
+	let mut selected_duplicate_aggregation = DuplicateAggregation::default();
+
+	let has_column = |name: &String| content.columns.iter().any(|each| *each.field == *name);
 
-	// We don't want to re-parse the header row
-	guarded_state.column_lookup = Some(HashMap::from_iter(
-		columns
+	// Loaded model works best with the same columns and periode it was trained with
+	if let Some(loaded_model) = &guarded_state.loaded_model {
+		if has_column(&loaded_model.datetime_column) {
+			selected_datetime_column = loaded_model.datetime_column.clone();
+		}
+		if has_column(&loaded_model.predictable_column) {
+			selected_predictable_column = loaded_model.predictable_column.clone();
+		}
+		if loaded_model
+			.target_columns
+			.iter()
+			.all(|each| has_column(each))
+		{
+			selected_target_columns = loaded_model.target_columns.clone();
+		}
+		if loaded_model
+			.feature_columns
+			.iter()
+			.all(|each| has_column(each))
+		{
+			selected_feature_columns = loaded_model.feature_columns.clone();
+		}
+		selected_series_column = loaded_model
+			.series_column
+			.clone()
+			.filter(|found| has_column(found));
+		if allowed_batch_periodes.contains(&loaded_model.batch_periode) {
+			selected_batch_periode = loaded_model.batch_periode;
+		}
+	}
+
+	// Previously submitted config of the same sheet wins, e.g. from a project file
+	let submitted_config = guarded_state
+		.preprocess_config
+		.as_ref()
+		.filter(|config| config.tab_name.as_deref() == is_spreadsheet.then_some(tab_name.as_str()));
+	if let Some(config) = submitted_config {
+		if has_column(&config.datetime_column) {
+			selected_datetime_column = config.datetime_column.clone();
+		}
+		if has_column(&config.predictable_column) {
+			selected_predictable_column = config.predictable_column.clone();
+		}
+		selected_target_columns = config
+			.target_columns
+			.iter()
+			.filter(|each| has_column(each))
+			.cloned()
+			.collect();
+		selected_feature_columns = config
+			.feature_columns
 			.iter()
-			.map(|each| each.field.clone())
-			.zip(0..column_count),
-	));
+			.filter(|each| has_column(each))
+			.cloned()
+			.collect();
+		selected_series_column = config
+			.series_column
+			.clone()
+			.filter(|found| has_column(found));
+		if allowed_batch_periodes.contains(&config.batch_periode) {
+			selected_batch_periode = config.batch_periode;
+		}
+		row_selection = config.row_selection.clone();
+		selected_duplicate_aggregation = config.duplicate_aggregation;
+	}
 
-	guarded_state.dropped_row_indices = dropped_row_indices;
+	guarded_state.column_lookup = Some(content.column_lookup);
+	guarded_state.datetime_format = content.datetime_format; // Numeric timestamp may be detected
+	guarded_state.column_units = content
+		.columns
+		.iter()
+		.filter_map(|each| Some((each.field.clone(), each.unit.clone()?)))
+		.collect();
+	guarded_state.dropped_row_indices = content.dropped_row_indices;
 
 	SheetInfo {
-		tab_name: selected_sheet.and(Some(tab_name)),
-		columns,
-		rows,
+		tab_name: is_spreadsheet.then_some(tab_name),
+		columns: content.columns,
+		rows: content.rows,
 		allowed_batch_periodes,
 		selected_datetime_column,
 		selected_predictable_column,
+		selected_target_columns,
+		selected_feature_columns,
+		selected_series_column,
 		selected_batch_periode,
 		row_selection,
+		selected_duplicate_aggregation,
+		datetime_format: guarded_state.datetime_format.clone(),
+		column_inferences: content.column_inferences,
+		sheet_layout: guarded_state.sheet_layout.clone(),
+		missing_values: guarded_state.missing_values.clone(),
 	}
 }
 
@@ -647,9 +467,9 @@ pub(crate) fn select_sheet(
 pub(crate) async fn submit_preprocess_config(
 	app: AppHandle,
 	state: State<'_, Mutex<AppState>>,
-	config: PreprocessConfig,
+	mut config: PreprocessConfig,
 ) -> Result<(), ()> {
-	let mut selected_source_data: Vec<(u64, f64)> = {
+	let (selected_source_data, loaded_model) = {
 		let mut guarded_state = match state.lock() {
 			Ok(ok) => ok,
 			Err(err) => {
@@ -670,34 +490,62 @@ pub(crate) async fn submit_preprocess_config(
 			return Err(());
 		}
 
-		let column_lookup = guarded_state
+		let app_state = &mut *guarded_state;
+		config.datetime_format = app_state.datetime_format.clone(); // Same one the sheet was read with
+		config.column_types = app_state.column_types.clone();
+		config.sheet_layout = app_state.sheet_layout.clone(); // Same offsets as the rows shown
+		config.missing_values = app_state.missing_values.clone();
+		let column_lookup = app_state
 			.column_lookup
 			.as_ref()
 			.expect("Forgot to set column_lookup in select_sheet");
 
-		let datetime_index = match column_lookup.get(&config.datetime_column) {
-			Some(found) => *found,
-			None => {
-				app.emit::<ErrorInfo>(
-					crate::event::DIALOG_ERROR,
-					ErrorInfo {
-						title: Cow::Borrowed(ERROR_MODIFIED),
-						message: String::from("Date/time column suddenly gone in this CSV file"),
-					},
-				)
-				.unwrap();
+		let mut selected_source_data = match extract_series(
+			&mut app_state.source_data,
+			column_lookup,
+			&app_state.dropped_row_indices,
+			&config,
+		) {
+			Ok(ok) => ok,
+			Err(err) => {
+				app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+					.unwrap();
 				return Err(());
 			}
 		};
 
-		let predictable_index = match column_lookup.get(&config.predictable_column) {
-			Some(found) => *found,
-			None => {
+		let imputation_summaries = impute_missing_values(&mut selected_source_data, &config);
+		if let Some(warning) = imputation_warning(&imputation_summaries) {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, warning)
+				.unwrap();
+		}
+
+		let merged_count =
+			aggregate_duplicate_timestamps(&mut selected_source_data, config.duplicate_aggregation);
+		if let Some(warning) = aggregation_warning(merged_count, config.duplicate_aggregation) {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, warning)
+				.unwrap();
+		}
+
+		(selected_source_data, app_state.loaded_model.clone())
+	};
+
+	let submitted_config = config.clone();
+
+	let preprocessed_result =
+		match tauri::async_runtime::spawn_blocking(move || match &loaded_model {
+			Some(found) => build_historical_data_like(selected_source_data, &config, found),
+			None => build_historical_data(selected_source_data, &config),
+		})
+		.await
+		{
+			Ok(ok) => ok,
+			Err(err) => {
 				app.emit::<ErrorInfo>(
 					crate::event::DIALOG_ERROR,
 					ErrorInfo {
-						title: Cow::Borrowed(ERROR_MODIFIED),
-						message: String::from("Predictable column suddenly gone in this CSV file"),
+						title: Cow::Borrowed("Cannot Create New Process Thread"),
+						message: err.to_string(),
 					},
 				)
 				.unwrap();
@@ -705,177 +553,11 @@ pub(crate) async fn submit_preprocess_config(
 			}
 		};
 
-		let dropped_row_indices = guarded_state.dropped_row_indices.clone();
-
-		match &mut guarded_state.source_data {
-			SourceData::None => unreachable!(),
-			SourceData::Csv(reader) => {
-				if let Err(err) = reader.seek(Position::new()) {
-					app.emit::<ErrorInfo>(
-						crate::event::DIALOG_ERROR,
-						ErrorInfo {
-							title: Cow::Borrowed(ERROR_RESET),
-							message: err.to_string(),
-						},
-					)
-					.unwrap();
-					return Err(());
-				}
-
-				let mut row_index = 0u64;
-				reader
-					.records()
-					.skip(1)
-					.filter(|_| {
-						let is_id_listed = config.row_selection.ids.contains(&row_index);
-						let is_dropped = dropped_row_indices.contains(&row_index);
-						row_index += 1;
-
-						!is_dropped
-							&& match config.row_selection.selection_type {
-								SelectionType::EXCLUDE => !is_id_listed,
-								SelectionType::INCLUDE => is_id_listed,
-							}
-					})
-					.filter_map(|each| {
-						let each_row = each.and_then(|ok| Ok(Some(ok))).unwrap_or_else(|_| None)?;
-
-						let x = parse_datetime(each_row.get(datetime_index)?)
-							.and_then(|ok| Ok(Some(ok)))
-							.unwrap_or_else(|_| None)?
-							.timestamp() as u64;
-
-						let y = each_row
-							.get(predictable_index)?
-							.parse::<f64>()
-							.and_then(|ok| Ok(Some(ok)))
-							.unwrap_or_else(|_| None)?;
-
-						Some((x, y))
-					})
-					.collect::<Vec<_>>()
-			}
-			SourceData::Spreadsheet(sheets) => {
-				if config.tab_name.is_none() {
-					app.emit::<ErrorInfo>(
-						crate::event::DIALOG_ERROR,
-						ErrorInfo {
-							title: Cow::Borrowed(ERROR_INCOMPLETE),
-							message: String::from(
-								"Because tab name is missing while loading spreadsheet",
-							),
-						},
-					)
-					.unwrap();
-					return Err(());
-				}
-
-				let tab_name = config.tab_name.unwrap();
-				let sheet = match sheets.worksheet_range(&tab_name) {
-					Ok(ok) => ok,
-					Err(err) => {
-						app.emit::<ErrorInfo>(
-							crate::event::DIALOG_ERROR,
-							ErrorInfo {
-								title: Cow::Borrowed(ERROR_MODIFIED),
-								message: err.to_string(),
-							},
-						)
-						.unwrap();
-						return Err(());
-					}
-				};
-
-				let mut row_index = 0u64;
-				sheet
-					.rows()
-					.skip(1)
-					.filter(|_| {
-						let is_id_listed = config.row_selection.ids.contains(&row_index);
-						let is_dropped = dropped_row_indices.contains(&row_index);
-						row_index += 1;
-
-						!is_dropped
-							&& match config.row_selection.selection_type {
-								SelectionType::EXCLUDE => !is_id_listed,
-								SelectionType::INCLUDE => is_id_listed,
-							}
-					})
-					.filter_map(|each_row| {
-						let datetime_cell = each_row.get(datetime_index)?;
-						let x = datetime_cell
-							.as_datetime()
-							.or(datetime_cell
-								.as_date()
-								.and_then(|found| Some(found.and_time(Local::now().time()))))
-							.or(datetime_cell
-								.as_time()
-								.and_then(|found| Some(Local::now().date_naive().and_time(found))))?
-							.and_local_timezone(Local)
-							.unwrap()
-							.timestamp() as u64;
-
-						let y = each_row.get(predictable_index)?.as_f64()?;
-
-						Some((x, y))
-					})
-					.collect::<Vec<_>>()
-			}
-		}
-	};
-
-	selected_source_data.sort_unstable_by_key(|each| each.0);
-
-	let batch_info = calculate_batch_info(&selected_source_data, config.batch_periode);
-
-	if batch_info.sequence_count < 2 {
-		app.emit::<ErrorInfo>(
-			crate::event::DIALOG_ERROR,
-			ErrorInfo {
-				title: Cow::Borrowed("Unable to Spot The Pattern"),
-				message: format!(
-					"Because data pattern is not there {}, try to change the quicker period",
-					config.batch_periode
-				),
-			},
-		)
-		.unwrap();
-		return Err(());
-	}
-
-	let first_timestamp = selected_source_data.first().cloned().unwrap_or_default().0;
-	let timestamp_interval = batch_info.interval;
-
-	let batches_result = match tauri::async_runtime::spawn_blocking(move || {
-		resample_to_batches(&selected_source_data, &batch_info)
-	})
-	.await
-	{
-		Ok(ok) => ok,
-		Err(err) => {
-			app.emit::<ErrorInfo>(
-				crate::event::DIALOG_ERROR,
-				ErrorInfo {
-					title: Cow::Borrowed("Cannot Create New Process Thread"),
-					message: err.to_string(),
-				},
-			)
-			.unwrap();
-			return Err(());
-		}
-	};
-
-	let batches = match batches_result {
+	let preprocessed_data = match preprocessed_result {
 		Ok(ok) => ok,
 		Err(err) => {
-			app.emit::<ErrorInfo>(
-				crate::event::DIALOG_ERROR,
-				ErrorInfo {
-					title: Cow::Borrowed("Re-sampling Failed"),
-					message: err.to_string(),
-				},
-			)
-			.unwrap();
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
 			return Err(());
 		}
 	};
@@ -896,11 +578,11 @@ pub(crate) async fn submit_preprocess_config(
 		}
 	};
 
-	guarded_state.preprocessed_data = Some(HistoricalData {
-		batches,
-		first_timestamp,
-		timestamp_interval,
-	});
+	guarded_state.preprocessed_data = Some(preprocessed_data);
+	guarded_state.preprocess_config = Some(submitted_config);
+	guarded_state.forecast = None;
+	guarded_state.forecast_horizon = None;
+	guarded_state.backtest = None;
 
 	Ok(())
 }
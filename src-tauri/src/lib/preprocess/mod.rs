@@ -18,5 +18,3 @@
 
 pub(super) mod command;
 pub(super) mod typedef;
-
-mod helper;
@@ -16,69 +16,14 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use std::{collections::HashMap, sync::Arc};
-use strum_macros::EnumIter;
 
-#[derive(Default, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
-#[serde(rename_all = "lowercase")]
-pub(crate) enum ColumnType {
-	#[default]
-	STRING,
-	NUMBER,
-	#[serde(rename = "dateTime")]
-	DATETIME,
-	BOOLEAN,
-}
-
-#[derive(Default, Deserialize, Serialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct ColumnInfo {
-	pub field: Arc<String>,
-	pub header_name: String,
-	#[serde(rename = "type")]
-	pub column_type: ColumnType,
-}
-
-#[derive(Default, PartialEq, Eq, EnumIter, Deserialize, Serialize, Clone, Copy, Debug)]
-#[serde(rename_all = "lowercase")]
-pub(crate) enum BatchPeriode {
-	MINUTELY,
-	HOURLY,
-	DAILY,
-	WEEKLY,
-	MONTHLY,
-	#[default]
-	YEARLY,
-}
-
-impl std::fmt::Display for BatchPeriode {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		match self {
-			BatchPeriode::MINUTELY => write!(f, "minutely"),
-			BatchPeriode::HOURLY => write!(f, "hourly"),
-			BatchPeriode::DAILY => write!(f, "daily"),
-			BatchPeriode::WEEKLY => write!(f, "weekly"),
-			BatchPeriode::MONTHLY => write!(f, "monthly"),
-			BatchPeriode::YEARLY => write!(f, "yearly"),
-		}
-	}
-}
-
-#[derive(Default, PartialEq, Eq, Deserialize, Serialize, Clone, Copy, Debug)]
-#[serde(rename_all = "lowercase")]
-pub(crate) enum SelectionType {
-	#[default]
-	EXCLUDE,
-	INCLUDE,
-}
-
-#[derive(Default, Serialize, Deserialize, Clone, Debug)]
-pub(crate) struct RowSelection {
-	pub ids: Vec<u64>,
-	#[serde(rename = "type")]
-	pub selection_type: SelectionType,
-}
+pub(crate) use crate::engine::typedef::{
+	BatchPeriode, ColumnInference, ColumnInfo, ColumnType, CsvDialect, DatetimeFormat,
+	DuplicateAggregation, MissingValueStrategy, PreprocessConfig, RowSelection, SelectionType,
+	SheetLayout, TrainSettings,
+};
 
 #[derive(Default, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -89,8 +34,16 @@ pub(crate) struct SheetInfo {
 	pub allowed_batch_periodes: Vec<BatchPeriode>,
 	pub selected_datetime_column: Arc<String>,
 	pub selected_predictable_column: Arc<String>,
+	pub selected_target_columns: Vec<Arc<String>>,
+	pub selected_feature_columns: Vec<Arc<String>>,
+	pub selected_series_column: Option<Arc<String>>,
 	pub selected_batch_periode: BatchPeriode,
 	pub row_selection: RowSelection,
+	pub selected_duplicate_aggregation: DuplicateAggregation,
+	pub datetime_format: DatetimeFormat,
+	pub column_inferences: Vec<ColumnInference>,
+	pub sheet_layout: SheetLayout,
+	pub missing_values: HashMap<Arc<String>, MissingValueStrategy>,
 }
 
 #[derive(Default, Serialize, Clone, Debug)]
@@ -98,29 +51,8 @@ pub(crate) struct SheetInfo {
 pub(crate) struct DataInfo {
 	pub name: String,
 	pub tabs: Option<Vec<String>>,
+	pub is_database: bool,               // Tab name can be a custom query as well
+	pub csv_dialect: Option<CsvDialect>, // Only set for CSV
 	pub sheet_info: SheetInfo,
-}
-
-#[derive(Default, Clone, Copy)]
-pub(super) struct ColumnCounter {
-	pub string: u64,
-	pub number: u64,
-	pub datetime: u64,
-	pub boolean: u64,
-}
-
-#[derive(Default, Deserialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct PreprocessConfig {
-	pub tab_name: Option<String>, // None only if CSV
-	pub datetime_column: Arc<String>,
-	pub predictable_column: Arc<String>,
-	pub batch_periode: BatchPeriode,
-	pub row_selection: RowSelection,
-}
-
-pub(super) struct BatchInfo {
-	pub sequence_size: u32,
-	pub sequence_count: u32,
-	pub interval: u32,
+	pub train_settings: TrainSettings, // Restored from project file, or the default one
 }
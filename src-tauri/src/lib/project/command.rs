@@ -0,0 +1,322 @@
+/*
+ * Project::Tarot, a simple LSTM implementation with GUI
+ * Copyright (C) 2025 Athaariq A. Ramadhani <foss@athaariq.my.id>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::{borrow::Cow, sync::Mutex};
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+
+use crate::engine::storage::{self, PROJECT_EXTENSION};
+use crate::engine::typedef::Project;
+use crate::typedef::{AppState, ErrorInfo};
+
+/// Embeds a copy of historical data by default, so the project still opens on other computer
+#[tauri::command]
+pub(crate) async fn save_project(
+	app: AppHandle,
+	state: State<'_, Mutex<AppState>>,
+	embed_source: Option<bool>,
+) -> Result<(), ()> {
+	let (tx, mut rx) = tauri::async_runtime::channel::<Option<FilePath>>(1024);
+
+	app.dialog()
+		.file()
+		.set_title("Save the project")
+		.add_filter("Project::Tarot Project File", &[PROJECT_EXTENSION])
+		.save_file(move |file_path| {
+			let _ = tx.blocking_send(file_path);
+		});
+
+	let file_path_option = rx.recv().await;
+	if file_path_option
+		.as_ref()
+		.is_none_or(|found| found.is_none())
+	{
+		return Ok(());
+	}
+
+	let file_path = file_path_option.unwrap().unwrap();
+	let target_path = match file_path.into_path() {
+		Ok(ok) => ok.with_extension(PROJECT_EXTENSION),
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("Cannot Parse File Path"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Ok(());
+		}
+	};
+
+	let mut project = {
+		let guarded_state = match state.lock() {
+			Ok(ok) => ok,
+			Err(err) => {
+				app.emit(crate::event::CORE_PANIC, ()).unwrap();
+				app.emit::<ErrorInfo>(
+					crate::event::DIALOG_ERROR,
+					ErrorInfo {
+						title: Cow::Borrowed("State Inaccessible before Saving Project"),
+						message: err.to_string(),
+					},
+				)
+				.unwrap();
+				return Err(());
+			}
+		};
+
+		let source_path = match &guarded_state.source_path {
+			Some(found) => found.clone(),
+			None => {
+				app.emit::<ErrorInfo>(
+					crate::event::DIALOG_ERROR,
+					ErrorInfo {
+						title: Cow::Borrowed("Cannot Save Project"),
+						message: String::from("There is no historical data to be saved"),
+					},
+				)
+				.unwrap();
+				return Err(());
+			}
+		};
+
+		Project {
+			source_path,
+			source_copy: None,
+			csv_dialect: guarded_state.csv_dialect.clone(),
+			preprocess_config: guarded_state.preprocess_config.clone(),
+			train_settings: guarded_state.train_settings.clone(),
+			trained_model: guarded_state
+				.trained_model
+				.as_ref()
+				.or(guarded_state.loaded_model.as_ref())
+				.cloned(),
+			forecast: guarded_state.forecast.clone(),
+			backtest: guarded_state.backtest.clone(),
+		}
+	};
+
+	let saved_result = match tauri::async_runtime::spawn_blocking(move || {
+		if embed_source.unwrap_or(true) {
+			project.source_copy = match std::fs::read(&project.source_path) {
+				Ok(ok) => Some(ok),
+				Err(err) => {
+					return Err(ErrorInfo {
+						title: Cow::Borrowed("Cannot Embed Historical Data"),
+						message: err.to_string(),
+					})
+				}
+			};
+		}
+
+		storage::save_project(&project, &target_path)
+	})
+	.await
+	{
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("Cannot Create New Process Thread"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Err(());
+		}
+	};
+
+	if let Err(err) = saved_result {
+		app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+			.unwrap();
+		return Err(());
+	}
+
+	Ok(())
+}
+
+/// Replaces current session, then moves to the furthest page the project has reached
+#[tauri::command]
+pub(crate) async fn open_project(
+	app: AppHandle,
+	state: State<'_, Mutex<AppState>>,
+) -> Result<(), ()> {
+	let (tx, mut rx) = tauri::async_runtime::channel::<Option<FilePath>>(1024);
+
+	app.dialog()
+		.file()
+		.set_title("Open a project")
+		.add_filter("Project::Tarot Project File", &[PROJECT_EXTENSION])
+		.add_filter("Other File Type", &["*"])
+		.pick_file(move |file_path| {
+			let _ = tx.blocking_send(file_path);
+		});
+
+	let file_path_option = rx.recv().await;
+	if file_path_option
+		.as_ref()
+		.is_none_or(|found| found.is_none())
+	{
+		return Ok(());
+	}
+
+	let file_path = file_path_option.unwrap().unwrap();
+	let project_path = match file_path.into_path() {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("Cannot Parse File Path"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Ok(());
+		}
+	};
+
+	let loaded_result = match tauri::async_runtime::spawn_blocking(move || {
+		let project = storage::load_project(&project_path)?;
+		let source_path = storage::restore_source_path(&project)?;
+
+		// Evaluate page backtests and exports with the same series the model was trained on
+		let reopened = match (
+			&project.preprocess_config,
+			&project.trained_model,
+			&project.forecast,
+		) {
+			(Some(config), Some(trained_model), Some(_)) => Some(storage::reopen_source(
+				&source_path,
+				&project.csv_dialect,
+				config,
+				trained_model,
+			)?),
+			_ => None,
+		};
+		Ok::<_, ErrorInfo>((project, source_path, reopened))
+	})
+	.await
+	{
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("Cannot Create New Process Thread"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Err(());
+		}
+	};
+
+	let (project, source_path, reopened) = match loaded_result {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
+			return Ok(());
+		}
+	};
+
+	let mut guarded_state = match state.lock() {
+		Ok(ok) => ok,
+		Err(err) => {
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("State Inaccessible on Open Project"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			return Err(());
+		}
+	};
+
+	*guarded_state = Default::default();
+	guarded_state.source_path = Some(source_path);
+	guarded_state.csv_dialect = project.csv_dialect;
+	guarded_state.datetime_format = project
+		.preprocess_config
+		.as_ref()
+		.map(|config| config.datetime_format.clone())
+		.unwrap_or_default();
+	guarded_state.column_types = project
+		.preprocess_config
+		.as_ref()
+		.map(|config| config.column_types.clone())
+		.unwrap_or_default();
+	guarded_state.sheet_layout = project
+		.preprocess_config
+		.as_ref()
+		.map(|config| config.sheet_layout.clone())
+		.unwrap_or_default();
+	guarded_state.missing_values = project
+		.preprocess_config
+		.as_ref()
+		.map(|config| config.missing_values.clone())
+		.unwrap_or_default();
+	guarded_state.preprocess_config = project.preprocess_config;
+	guarded_state.train_settings = project.train_settings;
+
+	match (project.trained_model, project.forecast, reopened) {
+		// Evaluate page shows the saved forecast instead of predicting again
+		(Some(trained_model), Some(forecast), Some(reopened)) => {
+			guarded_state.source_data = reopened.source_data;
+			guarded_state.column_lookup = Some(reopened.content.column_lookup);
+			guarded_state.column_units = reopened
+				.content
+				.columns
+				.iter()
+				.filter_map(|each| Some((each.field.clone(), each.unit.clone()?)))
+				.collect();
+			guarded_state.dropped_row_indices = reopened.content.dropped_row_indices;
+			guarded_state.preprocessed_data = Some(reopened.preprocessed_data);
+			guarded_state.trained_model = Some(trained_model);
+			guarded_state.forecast = Some(forecast);
+			guarded_state.backtest = project.backtest;
+			guarded_state.page_index = 3;
+		}
+		// Same as loading a model, training will be skipped
+		(Some(trained_model), _, _) => {
+			guarded_state.loaded_model = Some(trained_model);
+			guarded_state.page_index = 1;
+		}
+		(None, _, _) => guarded_state.page_index = 1,
+	}
+
+	if let Err(err) = app.emit(crate::event::PAGE_MOVE, guarded_state.page_index) {
+		app.emit::<ErrorInfo>(
+			crate::event::DIALOG_ERROR,
+			ErrorInfo {
+				title: Cow::Borrowed("Unable to Move Page after Open Project"),
+				message: err.to_string(),
+			},
+		)
+		.unwrap();
+		*guarded_state = Default::default();
+	}
+
+	Ok(())
+}
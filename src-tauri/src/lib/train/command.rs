@@ -16,32 +16,21 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use burn::backend::ndarray::NdArrayDevice;
-use burn::backend::Autodiff;
-use burn::backend::NdArray;
-use burn::module::AutodiffModule;
-use burn::prelude::*;
 use std::borrow::Cow;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, State};
 
-use crate::typedef::ErrorInfo;
-use crate::typedef::NormalParam;
+use crate::typedef::{AppProgress, ErrorInfo, TrainControl};
 
-use super::helper::*;
 use super::typedef::*;
 
 #[tauri::command]
 pub(crate) async fn start_train(
 	app: AppHandle,
 	state: State<'_, Mutex<crate::typedef::AppState>>,
+	settings: Option<TrainSettings>,
 ) -> Result<(), ()> {
-	let device = NdArrayDevice::Cpu;
-
-	let random_seed: u64 = rand::random();
-	Autodiff::<NdArray>::seed(random_seed);
-
-	let input_result = {
+	let (preprocessed_data, settings, control) = {
 		let mut guarded_state = match state.lock() {
 			Ok(ok) => ok,
 			Err(err) => {
@@ -58,37 +47,74 @@ pub(crate) async fn start_train(
 			}
 		};
 
-		if let None = guarded_state.preprocessed_data {
-			guarded_state.page_index = 1;
+		let preprocessed_data = match &guarded_state.preprocessed_data {
+			Some(found) => found.clone(),
+			None => {
+				guarded_state.page_index = 1;
+				app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
+					.unwrap();
+				return Err(());
+			}
+		};
+
+		// Loaded model is already trained, go straight to Evaluate page
+		if let Some(found) = guarded_state.loaded_model.clone() {
+			guarded_state.trained_model = Some(found);
+			guarded_state.page_index = 3;
 			app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
 				.unwrap();
+			return Ok(());
+		}
+
+		// Fallback to the settings restored from project file, then the default one
+		let settings = settings
+			.or(guarded_state.train_settings.clone())
+			.unwrap_or_default();
+		if let Err(err) = settings.validate() {
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
 			return Err(());
 		}
 
+		guarded_state.train_settings = Some(settings.clone());
+		guarded_state.train_progress = TrainProgress {
+			epoch_points: Vec::new(),
+			end_x: settings.max_epochs,
+			settings: settings.clone(),
+			stop_reason: None,
+			best_epoch: 0,
+			paused: false,
+		};
+
+		let control = Arc::new(TrainControl::default());
+		guarded_state.train_control = Some(control.clone());
+		guarded_state.keep_canceled_model = true;
+
 		guarded_state.page_index = 2;
 		app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
 			.unwrap();
 
-		let preprocessed_data = guarded_state
-			.preprocessed_data
-			.as_ref()
-			.unwrap()
-			.batches
-			.clone();
-
-		let cloned_device = device.clone();
-		tauri::async_runtime::spawn_blocking(move || {
-			send_batches_to_gpu(&preprocessed_data, &cloned_device)
-		})
+		(preprocessed_data, settings, control)
 	};
 
-	let input: TrainInput<Autodiff<NdArray>> = match input_result.await {
+	let mut progress = AppProgress { app: app.clone() };
+	let thread_control = control.clone();
+	let trained_model = match tauri::async_runtime::spawn_blocking(move || {
+		crate::engine::train::train(
+			&preprocessed_data,
+			&settings,
+			&thread_control,
+			&mut progress,
+		)
+	})
+	.await
+	{
 		Ok(ok) => ok,
 		Err(err) => {
 			app.emit::<ErrorInfo>(
 				crate::event::DIALOG_ERROR,
 				ErrorInfo {
-					title: Cow::Borrowed("Cannot Use GPU"),
+					title: Cow::Borrowed("Training Failed"),
 					message: err.to_string(),
 				},
 			)
@@ -98,50 +124,65 @@ pub(crate) async fn start_train(
 		}
 	};
 
-	let normal_param = NormalParam {
-		mean: input.mean,
-		stdev: input.stdev,
-	};
-
-	let cloned_app = app.clone();
-	let trained_model = match tauri::async_runtime::spawn_blocking(move || {
-		train_new_model(input, cloned_app, &device)
-	})
-	.await
-	{
+	let mut guarded_state = match state.lock() {
 		Ok(ok) => ok,
 		Err(err) => {
+			app.emit::<()>(crate::event::CORE_PANIC, ()).unwrap();
 			app.emit::<ErrorInfo>(
 				crate::event::DIALOG_ERROR,
 				ErrorInfo {
-					title: Cow::Borrowed("Training Failed"),
+					title: Cow::Borrowed("State Inaccessible after Training"),
 					message: err.to_string(),
 				},
 			)
 			.unwrap();
-			crate::restart(app, state);
 			return Err(());
 		}
 	};
 
-	let mut guarded_state = match state.lock() {
+	// Session has been restarted meanwhile, nothing to do with this model
+	match &guarded_state.train_control {
+		Some(found) if Arc::ptr_eq(found, &control) => guarded_state.train_control = None,
+		_ => return Ok(()),
+	};
+
+	// Data is too short to train on, back to Preprocess page
+	let trained_model = match trained_model {
 		Ok(ok) => ok,
 		Err(err) => {
-			app.emit::<()>(crate::event::CORE_PANIC, ()).unwrap();
+			app.emit::<ErrorInfo>(crate::event::DIALOG_ERROR, err)
+				.unwrap();
+			guarded_state.page_index = 1;
+			app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
+				.unwrap();
+			return Err(());
+		}
+	};
+
+	// Canceled without keeping the model, back to Preprocess page. Same if no epoch was done, since
+	// the weights are still random then.
+	let is_untrained = guarded_state.train_progress.best_epoch == 0;
+	if control.is_canceled() && (!guarded_state.keep_canceled_model || is_untrained) {
+		if guarded_state.keep_canceled_model {
 			app.emit::<ErrorInfo>(
 				crate::event::DIALOG_ERROR,
 				ErrorInfo {
-					title: Cow::Borrowed("State Inaccessible after Training"),
-					message: err.to_string(),
+					title: Cow::Borrowed("Nothing to Keep"),
+					message: String::from(
+						"Training was stopped before its first epoch, so there is no model to evaluate",
+					),
 				},
 			)
 			.unwrap();
-			return Err(());
 		}
-	};
 
-	guarded_state.trained_model = Some(trained_model.valid());
-	guarded_state.normal_param = Some(normal_param);
+		guarded_state.page_index = 1;
+		app.emit(crate::event::PAGE_MOVE, guarded_state.page_index)
+			.unwrap();
+		return Ok(());
+	}
+
+	guarded_state.trained_model = Some(trained_model);
 	guarded_state.page_index = 3;
 	if let Err(err) = app.emit(crate::event::PAGE_MOVE, guarded_state.page_index) {
 		app.emit::<ErrorInfo>(
@@ -184,3 +225,83 @@ pub(crate) fn get_train_progress(
 
 	guarded_state.train_progress.clone()
 }
+
+/// Stops training before the next epoch. If `keep_model` is set, the best model so far goes to
+/// Evaluate page, otherwise it is discarded. A model without any finished epoch is always discarded.
+#[tauri::command]
+pub(crate) fn cancel_train(
+	app: AppHandle,
+	state: State<'_, Mutex<crate::typedef::AppState>>,
+	keep_model: bool,
+) -> Result<(), ()> {
+	let mut guarded_state = lock_state(&app, &state)?;
+
+	let control = match &guarded_state.train_control {
+		Some(found) => found.clone(),
+		None => return Err(()),
+	};
+
+	guarded_state.keep_canceled_model = keep_model;
+	guarded_state.train_progress.paused = false;
+	control.cancel();
+
+	Ok(())
+}
+
+/// Holds training before the next epoch until [`resume_train`] or [`cancel_train`]
+#[tauri::command]
+pub(crate) fn pause_train(
+	app: AppHandle,
+	state: State<'_, Mutex<crate::typedef::AppState>>,
+) -> Result<(), ()> {
+	let mut guarded_state = lock_state(&app, &state)?;
+
+	let control = match &guarded_state.train_control {
+		Some(found) => found.clone(),
+		None => return Err(()),
+	};
+
+	control.pause();
+	guarded_state.train_progress.paused = true;
+
+	Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn resume_train(
+	app: AppHandle,
+	state: State<'_, Mutex<crate::typedef::AppState>>,
+) -> Result<(), ()> {
+	let mut guarded_state = lock_state(&app, &state)?;
+
+	let control = match &guarded_state.train_control {
+		Some(found) => found.clone(),
+		None => return Err(()),
+	};
+
+	control.resume();
+	guarded_state.train_progress.paused = false;
+
+	Ok(())
+}
+
+fn lock_state<'a>(
+	app: &AppHandle,
+	state: &'a State<'_, Mutex<crate::typedef::AppState>>,
+) -> Result<std::sync::MutexGuard<'a, crate::typedef::AppState>, ()> {
+	match state.lock() {
+		Ok(ok) => Ok(ok),
+		Err(err) => {
+			app.emit::<()>(crate::event::CORE_PANIC, ()).unwrap();
+			app.emit::<ErrorInfo>(
+				crate::event::DIALOG_ERROR,
+				ErrorInfo {
+					title: Cow::Borrowed("State Inaccessible while Controlling The Training"),
+					message: err.to_string(),
+				},
+			)
+			.unwrap();
+			Err(())
+		}
+	}
+}
@@ -17,3 +17,4 @@
  */
 
 pub(crate) const PROGRESS_NEW: &'static str = "App://train/progress/new";
+pub(crate) const PROGRESS_STOP: &'static str = "App://train/progress/stop";
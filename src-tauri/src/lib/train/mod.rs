@@ -18,6 +18,4 @@
 
 pub(super) mod command;
 pub(super) mod event;
-pub(super) mod helper;
-pub(super) mod model;
 pub(super) mod typedef;
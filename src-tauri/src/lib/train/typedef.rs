@@ -16,51 +16,32 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use burn::{
-	optim::AdamConfig,
-	tensor::{backend::AutodiffBackend, Tensor},
-};
 use serde::Serialize;
 
-use super::model::*;
-
-#[derive(Default, Serialize, Clone, Copy)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct IterativePoint {
-	pub x: u32,
-	pub y: f32,
-}
+pub(crate) use crate::engine::typedef::{IterativePoint, StopReason, TrainSettings};
 
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct TrainProgress {
-	pub confidence_points: Vec<IterativePoint>,
+	pub epoch_points: Vec<IterativePoint>,
 	pub end_x: u32,
+	pub settings: TrainSettings, // Effective settings of current training
+	pub stop_reason: Option<StopReason>, // None while still training
+	pub best_epoch: u32,         // Epoch of the weights which are kept
+	pub paused: bool,
 }
 
 impl Default for TrainProgress {
 	fn default() -> Self {
+		let settings = TrainSettings::default();
+
 		Self {
-			confidence_points: Default::default(),
-			end_x: 500,
+			epoch_points: Default::default(),
+			end_x: settings.max_epochs,
+			settings,
+			stop_reason: None,
+			best_epoch: 0,
+			paused: false,
 		}
 	}
 }
-
-#[derive(burn::config::Config)]
-pub(super) struct TrainingConfig {
-	pub model: LstmNetworkConfig,
-	pub optimizer: AdamConfig,
-
-	#[config(default = 1e-3)]
-	pub lr: f64,
-}
-
-pub(super) struct TrainInput<B: AutodiffBackend> {
-	pub train_tensor: Tensor<B, 3>,
-	pub train_target_tensor: Tensor<B, 2>,
-	pub valid_tensor: Tensor<B::InnerBackend, 3>,
-	pub valid_target_tensor: Tensor<B::InnerBackend, 2>,
-	pub mean: f64,
-	pub stdev: f64,
-}
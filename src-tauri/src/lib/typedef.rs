@@ -16,66 +16,69 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use burn::backend::NdArray;
-use chrono::{DateTime, Local};
-use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, collections::HashMap, fs::File, io::BufReader, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc, sync::Mutex};
+use tauri::{AppHandle, Emitter, Manager};
 
-use crate::train::model::LstmNetwork;
-
-#[derive(Default, Serialize, Clone)]
-pub(crate) struct ErrorInfo {
-	pub title: Cow<'static, str>,
-	pub message: String,
-}
-
-#[derive(Deserialize, Serialize, Clone, Debug)]
-#[serde(untagged)]
-pub(crate) enum CellValue {
-	String(String),
-	Number(f64),
-	RowID(u32),
-	DateTime(DateTime<Local>),
-	Boolean(bool),
-}
-
-impl Default for CellValue {
-	fn default() -> Self {
-		CellValue::String(String::new())
-	}
-}
-
-#[derive(Default)]
-pub(crate) enum SourceData {
-	#[default]
-	None,
-	Csv(csv::Reader<File>),
-	Spreadsheet(calamine::Sheets<BufReader<File>>),
-}
-
-#[derive(Default, Clone)]
-pub(crate) struct HistoricalData {
-	pub batches: Vec<Vec<f64>>,
-	pub first_timestamp: u64,
-	pub timestamp_interval: u32,
-}
-
-#[derive(Default, Clone)]
-pub(crate) struct NormalParam {
-	pub mean: f64,
-	pub stdev: f64,
-}
+pub(crate) use crate::engine::typedef::{
+	Backtest, CellValue, ColumnType, CsvDialectOverride, DatetimeFormat, ErrorInfo, Forecast,
+	ForecastHorizon, HistoricalData, MissingValueStrategy, NumberUnit, PreprocessConfig,
+	SheetLayout, SourceData, TrainControl, TrainSettings, TrainedModel,
+};
 
 #[derive(Default)]
 pub(crate) struct AppState {
 	pub source_path: Option<PathBuf>,
 	pub source_data: SourceData,
+	pub csv_dialect: CsvDialectOverride,
+	pub datetime_format: DatetimeFormat,
+	pub column_types: HashMap<Arc<String>, ColumnType>, // Chosen by user instead of the majority
+	pub sheet_layout: SheetLayout,
+	pub missing_values: HashMap<Arc<String>, MissingValueStrategy>, // Drop if unset
 	pub dropped_row_indices: Vec<u64>,
 	pub column_lookup: Option<HashMap<Arc<String>, usize>>,
-	pub preprocessed_data: Option<HistoricalData>,
+	pub column_units: HashMap<Arc<String>, NumberUnit>, // Restored when exporting the forecast
+	pub preprocessed_data: Option<Vec<HistoricalData>>, // One per series
 	pub train_progress: super::train::typedef::TrainProgress,
-	pub trained_model: Option<LstmNetwork<NdArray>>,
-	pub normal_param: Option<NormalParam>,
-	pub predicted_data: Option<Vec<(DateTime<Local>, f64)>>,
+	pub train_control: Option<Arc<TrainControl>>, // Only set while training
+	pub keep_canceled_model: bool,
+	pub trained_model: Option<TrainedModel>,
+	pub loaded_model: Option<TrainedModel>, // Skips training when set
+	pub preprocess_config: Option<PreprocessConfig>,
+	pub train_settings: Option<TrainSettings>,
+	pub forecast: Option<Forecast>,
+	pub forecast_horizon: Option<ForecastHorizon>, // None means the default horizon
+	pub backtest: Option<Backtest>,
 	pub page_index: u8,
 }
+
+/// Forwards engine progress to ReactJS
+pub(crate) struct AppProgress {
+	pub app: AppHandle,
+}
+
+impl crate::engine::progress::Progress for AppProgress {
+	fn on_train_progress(&mut self, point: crate::engine::typedef::IterativePoint) {
+		let _ = self
+			.app
+			.emit(crate::train::event::PROGRESS_NEW, point.clone());
+
+		let state = self.app.state::<Mutex<AppState>>();
+		if let Ok(mut guarded_state) = state.lock() {
+			guarded_state.train_progress.epoch_points.push(point);
+		};
+	}
+
+	fn on_train_stop(&mut self, reason: crate::engine::typedef::StopReason, best_epoch: u32) {
+		let _ = self.app.emit(crate::train::event::PROGRESS_STOP, reason);
+
+		let state = self.app.state::<Mutex<AppState>>();
+		if let Ok(mut guarded_state) = state.lock() {
+			guarded_state.train_progress.stop_reason = Some(reason);
+			guarded_state.train_progress.best_epoch = best_epoch;
+		};
+	}
+
+	fn on_predict_progress(&mut self, percent: f64) {
+		let _ = self.app.emit(crate::evaluate::event::PROGRESS, percent);
+	}
+}
@@ -29,6 +29,7 @@ import spadeIcon from '../assets/cards.svg';
 
 export default function DataPage(props: BoxProps) {
 	const [loading, setLoading] = useState(false);
+	const [modelName, setModelName] = useState<string | null>(null);
 
 	const disabled = useMemo(
 		() => (typeof props.tabIndex === 'number' && props.tabIndex < 0) || loading,
@@ -42,6 +43,24 @@ export default function DataPage(props: BoxProps) {
 		});
 	}, [setLoading]);
 
+	const handleClickLoadModel = useCallback(() => {
+		setLoading(true);
+		invoke<string | null>('load_model')
+			.then((name) => {
+				if (name !== null) setModelName(name);
+			})
+			.finally(() => {
+				setLoading(false);
+			});
+	}, [setLoading, setModelName]);
+
+	const handleClickOpenProject = useCallback(() => {
+		setLoading(true);
+		invoke<void>('open_project').finally(() => {
+			setLoading(false);
+		});
+	}, [setLoading]);
+
 	return (
 		<Box
 			{...props}
@@ -65,14 +84,37 @@ export default function DataPage(props: BoxProps) {
 			<Typography component="p" variant="h5">
 				Let's start by understanding past historical data
 			</Typography>
-			<Button
-				variant="contained"
-				color="secondary"
-				onClick={handleClickLoad}
-				{...{ disabled, loading }}
-			>
-				Load File
-			</Button>
+			<Box sx={{ display: 'flex', flexDirection: 'row', gap: '16px' }}>
+				<Button
+					variant="contained"
+					color="secondary"
+					onClick={handleClickLoad}
+					{...{ disabled, loading }}
+				>
+					Load File
+				</Button>
+				<Button
+					variant="outlined"
+					color="secondary"
+					onClick={handleClickLoadModel}
+					{...{ disabled }}
+				>
+					Load Model
+				</Button>
+				<Button
+					variant="outlined"
+					color="secondary"
+					onClick={handleClickOpenProject}
+					{...{ disabled }}
+				>
+					Open Project
+				</Button>
+			</Box>
+			{modelName && (
+				<Typography component="p" variant="body2">
+					Training will be skipped, forecasting with <strong>{modelName}</strong>
+				</Typography>
+			)}
 		</Box>
 	);
 }
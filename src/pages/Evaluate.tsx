@@ -18,7 +18,7 @@
 
 import type { BoxProps } from '@mui/material/Box';
 
-import { useState, useMemo, useEffect, useCallback } from 'react';
+import { Fragment, useState, useMemo, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 
@@ -27,11 +27,18 @@ import CircularProgress from '@mui/material/CircularProgress';
 import Typography from '@mui/material/Typography';
 import Fade from '@mui/material/Fade';
 import Button from '@mui/material/Button';
+import TextField from '@mui/material/TextField';
+import Select from '@mui/material/Select';
+import MenuItem from '@mui/material/MenuItem';
 
 import { LineChart } from '@mui/x-charts/LineChart';
 
 import SaveIcon from '@mui/icons-material/Save';
+import ModelIcon from '@mui/icons-material/Memory';
+import ProjectIcon from '@mui/icons-material/Inventory2';
 import RestartIcon from '@mui/icons-material/RotateLeft';
+import BacktestIcon from '@mui/icons-material/History';
+import ForecastIcon from '@mui/icons-material/Update';
 
 type RawComparisonPoint = {
 	x: string;
@@ -39,35 +46,126 @@ type RawComparisonPoint = {
 	y1: number;
 };
 
-interface EvaluationReport {
-	confidence: number;
+interface ForecastMetrics {
+	mae: number;
+	rmse: number;
+	mape: number | null;
+	smape: number | null;
+	r2: number | null;
+}
+
+type HorizonUnit = 'steps' | 'minutely' | 'hourly' | 'daily' | 'weekly' | 'monthly' | 'yearly';
+
+type ForecastHorizon =
+	| { type: 'steps'; count: number }
+	| { type: 'duration'; amount: number; unit: Exclude<HorizonUnit, 'steps'> };
+
+const horizonUnitLabels: Record<HorizonUnit, string> = {
+	steps: 'steps',
+	minutely: 'minutes',
+	hourly: 'hours',
+	daily: 'days',
+	weekly: 'weeks',
+	monthly: 'months',
+	yearly: 'years'
+};
+
+interface BacktestSettings {
+	holdout: number;
+	origins: number;
+}
+
+interface Backtest {
+	settings: BacktestSettings;
+	heldOut: number;
+	metrics: ForecastMetrics;
+	origins: { seriesId: string | null; x: string; metrics: ForecastMetrics }[];
+	graphs: { seriesId: string | null; column: string; graph: RawComparisonPoint[] }[];
+}
+
+interface TargetReport {
+	seriesId: string | null;
+	column: string;
 	graph: RawComparisonPoint[];
 	highPeak?: RawComparisonPoint;
 	lowPeak?: RawComparisonPoint;
 }
 
+interface ColumnMetrics {
+	column: string;
+	metrics: ForecastMetrics;
+}
+
+interface EvaluationReport {
+	metrics: ColumnMetrics[];
+	targets: TargetReport[];
+	backtest: Backtest | null;
+}
+
+function formatMetrics(metrics: ForecastMetrics) {
+	const { mae, rmse, mape, smape, r2 } = metrics;
+	const round = (value: number | null) =>
+		value === null ? 'n/a' : (Math.round(value * 10000) / 10000).toString();
+
+	return [
+		`MAE ${round(mae)}`,
+		`RMSE ${round(rmse)}`,
+		`MAPE ${round(mape)}%`,
+		`sMAPE ${round(smape)}%`,
+		`R² ${round(r2)}`
+	].join(' · ');
+}
+
 export default function EvaluatePage(props: BoxProps) {
 	const [report, setReport] = useState<EvaluationReport | null>(null);
 	const [isSaving, setSaving] = useState(false);
 	const [predictProgress, setPredictProgress] = useState(0);
+	const [backtestSettings, setBacktestSettings] = useState<BacktestSettings>({
+		holdout: 10,
+		origins: 1
+	});
+	const [isBacktesting, setBacktesting] = useState(false);
+	const [horizonAmount, setHorizonAmount] = useState(30);
+	const [horizonUnit, setHorizonUnit] = useState<HorizonUnit>('steps');
+	const [showBacktest, setShowBacktest] = useState(false);
+	const [targetIndex, setTargetIndex] = useState(0);
 
 	const pageDisabled = useMemo(
 		() => typeof props.tabIndex === 'number' && props.tabIndex < 0,
 		[props.tabIndex]
 	);
 
-	const confidence = useMemo(
-		() => Math.round((report?.confidence ?? 0) * 100) / 100,
-		[report?.confidence]
+	// One line per predicted column, the predictable column first
+	const metricsLines = useMemo(
+		() =>
+			report ? report.metrics.map((each) => `${each.column}: ${formatMetrics(each.metrics)}`) : [],
+		[report?.metrics]
+	);
+
+	const backtestText = useMemo(
+		() => (report?.backtest ? formatMetrics(report.backtest.metrics) : ''),
+		[report?.backtest]
 	);
 
+	const target = report?.targets[targetIndex] ?? report?.targets[0];
+
+	// Backtest only covers the predictable column of each series
+	const backtestGraph = useMemo(
+		() =>
+			report?.backtest?.graphs.find((each) => each.seriesId === (target?.seriesId ?? null)) ??
+			report?.backtest?.graphs[0],
+		[report?.backtest, target?.seriesId]
+	);
+
+	const isShowingBacktest = showBacktest && !!backtestGraph;
+
 	const dataset = useMemo(
 		() =>
-			report?.graph.map((each) => ({
+			(isShowingBacktest ? backtestGraph?.graph : target?.graph)?.map((each) => ({
 				...each,
 				x: new Date(each.x)
 			})),
-		[report?.graph]
+		[target, backtestGraph, isShowingBacktest]
 	);
 
 	const formatter = useMemo(
@@ -86,17 +184,17 @@ export default function EvaluatePage(props: BoxProps) {
 	);
 
 	const highPeak = useMemo(
-		() => (report?.highPeak ? formatter.format(new Date(report.highPeak.x)) : undefined),
-		[report?.highPeak]
+		() => (target?.highPeak ? formatter.format(new Date(target.highPeak.x)) : undefined),
+		[target?.highPeak]
 	);
 
 	const lowPeak = useMemo(
-		() => (report?.lowPeak ? formatter.format(new Date(report.lowPeak.x)) : undefined),
-		[report?.lowPeak]
+		() => (target?.lowPeak ? formatter.format(new Date(target.lowPeak.x)) : undefined),
+		[target?.lowPeak]
 	);
 
 	const isPredicting = pageDisabled || report === null;
-	const isBusy = isPredicting || isSaving;
+	const isBusy = isPredicting || isSaving || isBacktesting;
 
 	useEffect(() => {
 		setReport(null);
@@ -126,6 +224,49 @@ export default function EvaluatePage(props: BoxProps) {
 		});
 	}, [setSaving]);
 
+	const handleSaveModel = useCallback(() => {
+		setSaving(true);
+		invoke<void>('save_model').finally(() => {
+			setSaving(false);
+		});
+	}, [setSaving]);
+
+	const handleSaveProject = useCallback(() => {
+		setSaving(true);
+		invoke<void>('save_project').finally(() => {
+			setSaving(false);
+		});
+	}, [setSaving]);
+
+	const handleBacktest = useCallback(() => {
+		setBacktesting(true);
+		invoke<EvaluationReport>('get_evaluation', { backtestSettings })
+			.then((value) => {
+				setReport(value);
+				setShowBacktest(!!value.backtest);
+			})
+			.finally(() => {
+				setBacktesting(false);
+			});
+	}, [backtestSettings, setBacktesting, setReport, setShowBacktest]);
+
+	const handleForecast = useCallback(() => {
+		const horizon: ForecastHorizon =
+			horizonUnit === 'steps'
+				? { type: 'steps', count: horizonAmount }
+				: { type: 'duration', amount: horizonAmount, unit: horizonUnit };
+
+		setBacktesting(true);
+		invoke<EvaluationReport>('get_evaluation', { horizon })
+			.then((value) => {
+				setReport(value);
+				setShowBacktest(false);
+			})
+			.finally(() => {
+				setBacktesting(false);
+			});
+	}, [horizonAmount, horizonUnit, setBacktesting, setReport, setShowBacktest]);
+
 	function handleRestart() {
 		invoke<void>('restart');
 	}
@@ -176,16 +317,42 @@ export default function EvaluatePage(props: BoxProps) {
 						}
 					]}
 					series={[
-						{ dataKey: 'y0', label: 'Historical Data', showMark: false },
-						{ dataKey: 'y1', label: 'Predicted', showMark: false }
+						{
+							dataKey: 'y0',
+							label: isShowingBacktest ? 'Actual' : 'Historical Data',
+							showMark: false
+						},
+						{
+							dataKey: 'y1',
+							label: isShowingBacktest ? 'Backtested' : 'Predicted',
+							showMark: false
+						}
 					]}
 					skipAnimation
 					grid={{ horizontal: true, vertical: false }}
 					{...{ dataset }}
 				/>
+				{(report?.targets.length ?? 0) > 1 ? (
+					<Select
+						size="small"
+						sx={{ mb: '16px' }}
+						value={targetIndex}
+						onChange={(e) => {
+							setTargetIndex(Number(e.target.value));
+						}}
+						disabled={isBusy}
+					>
+						{report?.targets.map((each, index) => (
+							<MenuItem key={`${each.seriesId}/${each.column}`} value={index}>
+								{each.seriesId ? `${each.seriesId} · ${each.column}` : each.column}
+							</MenuItem>
+						))}
+					</Select>
+				) : (
+					''
+				)}
 				<Typography sx={{ mb: '16px' }}>
-					Within <strong>{confidence}% confidence</strong>
-					{highPeak || lowPeak ? ', I predicted ' : ''}
+					{highPeak || lowPeak ? 'I predicted ' : ''}
 					{highPeak ? (
 						<>
 							the next <strong>highest peak</strong> is on <strong>{highPeak}</strong>
@@ -202,6 +369,112 @@ export default function EvaluatePage(props: BoxProps) {
 						''
 					)}
 				</Typography>
+				<Typography variant="body2" sx={{ mb: '16px' }}>
+					Validation accuracy:
+					{metricsLines.map((each, index) => (
+						<Fragment key={index}>
+							<br />
+							{each}
+						</Fragment>
+					))}
+					{backtestText ? (
+						<>
+							<br />
+							Backtest accuracy over {report?.backtest?.origins.length} origin(s) within the last{' '}
+							{report?.backtest?.heldOut} steps held out from training: {backtestText}
+						</>
+					) : (
+						''
+					)}
+				</Typography>
+				<Box
+					sx={{
+						display: 'flex',
+						flexDirection: 'row',
+						justifyContent: 'center',
+						alignItems: 'center',
+						gap: '16px',
+						mb: '16px'
+					}}
+				>
+					<TextField
+						type="number"
+						size="small"
+						label="Forecast the next"
+						value={horizonAmount}
+						onChange={(e) => setHorizonAmount(Math.max(1, parseInt(e.target.value) || 1))}
+						disabled={isBusy}
+					/>
+					<Select
+						size="small"
+						value={horizonUnit}
+						onChange={(e) => setHorizonUnit(e.target.value as HorizonUnit)}
+						disabled={isBusy}
+					>
+						{(Object.keys(horizonUnitLabels) as HorizonUnit[]).map((each) => (
+							<MenuItem key={each} value={each}>
+								{horizonUnitLabels[each]}
+							</MenuItem>
+						))}
+					</Select>
+					<Button
+						variant="outlined"
+						color="secondary"
+						startIcon={<ForecastIcon />}
+						onClick={handleForecast}
+						disabled={isBusy}
+					>
+						Forecast
+					</Button>
+					<TextField
+						type="number"
+						size="small"
+						label="Hidden periods"
+						value={backtestSettings.holdout}
+						onChange={(e) =>
+							setBacktestSettings({
+								...backtestSettings,
+								holdout: Math.max(1, parseInt(e.target.value) || 1)
+							})
+						}
+						disabled={isBusy}
+					/>
+					<TextField
+						type="number"
+						size="small"
+						label="Origins"
+						value={backtestSettings.origins}
+						onChange={(e) =>
+							setBacktestSettings({
+								...backtestSettings,
+								origins: Math.max(1, parseInt(e.target.value) || 1)
+							})
+						}
+						disabled={isBusy}
+					/>
+					<Button
+						variant="outlined"
+						color="secondary"
+						startIcon={<BacktestIcon />}
+						onClick={handleBacktest}
+						disabled={isBusy}
+						loading={isBacktesting}
+					>
+						Backtest
+					</Button>
+					{report?.backtest ? (
+						<Button
+							variant="text"
+							color="secondary"
+							onClick={() => setShowBacktest(!showBacktest)}
+							disabled={isBusy}
+						>
+							{showBacktest ? 'Show Forecast' : 'Show Backtest'}
+						</Button>
+					) : (
+						''
+					)}
+				</Box>
 				<Box
 					sx={{
 						display: 'flex',
@@ -221,6 +494,24 @@ export default function EvaluatePage(props: BoxProps) {
 					>
 						Save
 					</Button>
+					<Button
+						variant="outlined"
+						color="secondary"
+						startIcon={<ModelIcon />}
+						onClick={handleSaveModel}
+						disabled={isBusy}
+					>
+						Save Model
+					</Button>
+					<Button
+						variant="outlined"
+						color="secondary"
+						startIcon={<ProjectIcon />}
+						onClick={handleSaveProject}
+						disabled={isBusy}
+					>
+						Save Project
+					</Button>
 					<Button
 						variant="outlined"
 						color="secondary"
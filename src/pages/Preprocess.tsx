@@ -33,6 +33,7 @@ import FormControl from '@mui/material/FormControl';
 import InputLabel from '@mui/material/InputLabel';
 import Select from '@mui/material/Select';
 import MenuItem from '@mui/material/MenuItem';
+import TextField from '@mui/material/TextField';
 import Skeleton from '@mui/material/Skeleton';
 
 import { DataGrid } from '@mui/x-data-grid';
@@ -41,12 +42,21 @@ import NextIcon from '@mui/icons-material/ArrowForward';
 
 type ColumnType = 'string' | 'number' | 'dateTime' | 'boolean';
 
+interface NumberUnit {
+	prefix: string;
+	suffix: string;
+	magnitude: string | null;
+}
+
 interface ColumnInfo {
 	field: string;
 	headerName: string;
 	type: ColumnType;
+	unit: NumberUnit | null;
 }
 
+const MAGNITUDE_SCALES: Record<string, number> = { K: 1e3, M: 1e6, B: 1e9, T: 1e12 };
+
 type RowInfo = Record<string, string | number | Date | boolean>;
 
 type BatchPeriode = 'Minutely' | 'Hourly' | 'Daily' | 'Weekly' | 'Monthly' | 'Yearly';
@@ -63,22 +73,110 @@ interface SheetInfo {
 	allowedBatchPeriodes: BatchPeriode[];
 	selectedDatetimeColumn: string;
 	selectedPredictableColumn: string;
+	selectedTargetColumns: string[];
+	selectedFeatureColumns: string[];
+	selectedSeriesColumn: string | null;
 	selectedBatchPeriode: BatchPeriode;
 	rowSelection: RowSelection;
+	selectedDuplicateAggregation: DuplicateAggregation;
+	datetimeFormat: DatetimeFormat;
+	columnInferences: ColumnInference[];
+	sheetLayout: SheetLayout;
+	missingValues: Record<string, MissingValueStrategy>;
+}
+
+interface ColumnCounter {
+	string: number;
+	number: number;
+	datetime: number;
+	boolean: number;
+}
+
+interface ColumnInference {
+	field: string;
+	counts: ColumnCounter;
+	isOverridden: boolean;
+	isImputed: boolean;
+	offendingCount: number;
+	offendingSamples: { rowId: number; value: string }[];
+}
+
+type DuplicateAggregation = 'mean' | 'sum' | 'first' | 'last' | 'min' | 'max';
+
+type MissingValueStrategy =
+	| { type: 'drop' | 'forwardFill' | 'backwardFill' | 'linear' | 'mean' }
+	| { type: 'constant'; value: number };
+
+type NumericTimestamp = 'seconds' | 'milliseconds' | 'microseconds' | 'excelSerial';
+
+interface DatetimeFormat {
+	column: string | null;
+	format: string | null;
+	timezone: string | null;
+	numeric: NumericTimestamp | null;
+}
+
+type HeaderCombine = 'join' | 'first' | 'last';
+
+interface SheetLayout {
+	headerRow: number;
+	headerRows: number;
+	skipRows: number;
+	headerCombine: HeaderCombine;
+	headerSeparator: string;
+}
+
+interface CsvDialect {
+	delimiter: string;
+	quote: string;
+	hasHeader: boolean;
+	encoding: string;
+	decimalComma: boolean;
 }
 
 interface DataInfo {
 	name: string;
 	tabs?: string[];
+	isDatabase: boolean;
+	csvDialect: CsvDialect | null;
 	sheetInfo: SheetInfo;
+	trainSettings: TrainSettings;
 }
 
+const delimiterOptions = [
+	{ value: ',', label: 'Comma (,)' },
+	{ value: ';', label: 'Semicolon (;)' },
+	{ value: '\t', label: 'Tab' },
+	{ value: '|', label: 'Pipe (|)' }
+];
+
+const encodingOptions = ['utf-8', 'utf-16le', 'utf-16be', 'windows-1252', 'iso-8859-2', 'shift_jis'];
+
 interface PreprocessConfig {
 	tabName?: string;
 	datetimeColumn: string;
 	predictableColumn: string;
+	targetColumns: string[];
+	featureColumns: string[];
+	seriesColumn?: string;
 	batchPeriode: BatchPeriode;
 	rowSelection: RowSelection;
+	duplicateAggregation: DuplicateAggregation;
+}
+
+interface TrainSettings {
+	hiddenSize: number;
+	numLayers: number;
+	dropout: number;
+	bidirectional: boolean;
+	learningRate: number;
+	batchSize: number;
+	maxEpochs: number;
+	clipNorm: number | null;
+	patience: number;
+	minDelta: number;
+	minEpochs: number;
+	seriesEmbedding: boolean;
 }
 
 export default function PreprocessPage(props: BoxProps) {
@@ -89,17 +187,56 @@ export default function PreprocessPage(props: BoxProps) {
 	const [isSubmitting, setSubmitting] = useState(false);
 	const [name, setName] = useState<string>('');
 	const [tabs, setTabs] = useState<string[]>([]);
+	const [isDatabase, setDatabase] = useState(false);
+	const [customQuery, setCustomQuery] = useState<string>('');
+	const [csvDialect, setCsvDialect] = useState<CsvDialect | null>(null);
+	const [columnInferences, setColumnInferences] = useState<ColumnInference[]>([]);
+	const [inspectedField, setInspectedField] = useState<string>('');
+	const [missingValues, setMissingValues] = useState<Record<string, MissingValueStrategy>>({});
+	const [fillConstant, setFillConstant] = useState<string>('0');
+	const [datetimeFormat, setDatetimeFormat] = useState<DatetimeFormat>({
+		column: null,
+		format: null,
+		timezone: null,
+		numeric: null
+	});
+	const [sheetLayout, setSheetLayout] = useState<SheetLayout>({
+		headerRow: 0,
+		headerRows: 1,
+		skipRows: 0,
+		headerCombine: 'join',
+		headerSeparator: ' '
+	});
 	const [columns, setColumns] = useState<ColumnInfo[]>([]);
 	const [rows, setRows] = useState<RowInfo[]>([]);
 	const [allowedBatchPeriodes, setAllowedBatchPeriodes] = useState<BatchPeriode[]>([]);
 	const [selectedTab, setSelectedTab] = useState<string>('');
 	const [selectedDatetime, setSelectedDatetime] = useState<string>('');
 	const [selectedPredictable, setSelectedPredictable] = useState<string>('');
+	const [selectedTargets, setSelectedTargets] = useState<string[]>([]);
+	const [selectedFeatures, setSelectedFeatures] = useState<string[]>([]);
+	const [selectedSeries, setSelectedSeries] = useState<string>('');
 	const [selectedBatchPeriode, setSelectedBatchPeriode] = useState<BatchPeriode | ''>('');
+	const [selectedDuplicateAggregation, setSelectedDuplicateAggregation] =
+		useState<DuplicateAggregation>('mean');
 	const [rowSelection, setRowSelection] = useState<RowSelection>({
 		type: 'exclude',
 		ids: []
 	});
+	const [trainSettings, setTrainSettings] = useState<TrainSettings>({
+		hiddenSize: 128,
+		numLayers: 1,
+		dropout: 0.1,
+		bidirectional: false,
+		learningRate: 1e-3,
+		batchSize: 32,
+		maxEpochs: 500,
+		clipNorm: 1.0,
+		patience: 20,
+		minDelta: 0.0,
+		minEpochs: 50,
+		seriesEmbedding: false
+	});
 
 	const gridApiRef = useRef<GridApi>(null);
 
@@ -171,6 +308,60 @@ export default function PreprocessPage(props: BoxProps) {
 		[disabled, columns]
 	);
 
+	const targetSelections = useMemo(
+		() =>
+			disabled
+				? []
+				: columns
+						.filter((each) => each.type === 'number' && each.field !== selectedPredictable)
+						.map((each) => (
+							<MenuItem key={each.field} value={each.field}>
+								{each.headerName ?? each.field}
+							</MenuItem>
+						)),
+		[disabled, columns, selectedPredictable]
+	);
+
+	// Predicted columns are always fed into the model, so they are not listed as features
+	const featureSelections = useMemo(
+		() =>
+			disabled
+				? []
+				: columns
+						.filter(
+							(each) =>
+								each.type === 'number' &&
+								each.field !== selectedPredictable &&
+								!selectedTargets.includes(each.field)
+						)
+						.map((each) => (
+							<MenuItem key={each.field} value={each.field}>
+								{each.headerName ?? each.field}
+							</MenuItem>
+						)),
+		[disabled, columns, selectedPredictable, selectedTargets]
+	);
+
+	// Any column but the date/time one can tell the series apart
+	const seriesSelections = useMemo(
+		() =>
+			disabled
+				? []
+				: [
+						<MenuItem key="" value="">
+							<em>None, only one series</em>
+						</MenuItem>,
+						...columns
+							.filter((each) => each.type !== 'dateTime')
+							.map((each) => (
+								<MenuItem key={each.field} value={each.field}>
+									{each.headerName ?? each.field}
+								</MenuItem>
+							))
+				  ],
+		[disabled, columns]
+	);
+
 	const batchPeriode = useMemo(
 		() =>
 			disabled
@@ -229,7 +420,7 @@ export default function PreprocessPage(props: BoxProps) {
 	);
 
 	const handleSelectTab = useCallback(
-		(event: SelectChangeEvent<string>) => {
+		(event: SelectChangeEvent<string> | { target: { value: string } }) => {
 			if (disabled) return;
 			let value = event.target.value;
 			setSelectedTab(value);
@@ -251,7 +442,22 @@ export default function PreprocessPage(props: BoxProps) {
 	);
 
 	function changeSheetInfo(sheetInfo: SheetInfo) {
-		setColumns(sheetInfo.columns);
+		setColumns(
+			sheetInfo.columns.map((each) => {
+				let unit = each.unit;
+				if (!unit) return each;
+
+				// Show numbers the same way they are written in the file
+				const scale = MAGNITUDE_SCALES[unit.magnitude ?? ''] ?? 1;
+				return {
+					...each,
+					valueFormatter: (value?: number) =>
+						value === undefined
+							? ''
+							: `${unit.prefix}${value / scale}${unit.magnitude ?? ''}${unit.suffix}`
+				};
+			})
+		);
 		setRows(
 			sheetInfo.rows.map((each) => {
 				let datetimeKeys = Object.keys(each).filter((eachKey) =>
@@ -274,14 +480,128 @@ export default function PreprocessPage(props: BoxProps) {
 		setSelectedTab(sheetInfo.tabName ?? '');
 		setSelectedDatetime(sheetInfo.selectedDatetimeColumn);
 		setSelectedPredictable(sheetInfo.selectedPredictableColumn);
+		setSelectedTargets(sheetInfo.selectedTargetColumns);
+		setSelectedFeatures(sheetInfo.selectedFeatureColumns);
+		setSelectedSeries(sheetInfo.selectedSeriesColumn ?? '');
 		setSelectedBatchPeriode(sheetInfo.selectedBatchPeriode);
 		setRowSelection(sheetInfo.rowSelection);
+		setSelectedDuplicateAggregation(sheetInfo.selectedDuplicateAggregation);
+		setDatetimeFormat(sheetInfo.datetimeFormat);
+		setColumnInferences(sheetInfo.columnInferences);
+		setSheetLayout(sheetInfo.sheetLayout);
+		setMissingValues(sheetInfo.missingValues);
+	}
+
+	const inspectedColumn = columns.find((each) => each.field === inspectedField);
+	const inspectedInference = columnInferences.find((each) => each.field === inspectedField);
+	const inspectedStrategy = missingValues[inspectedField] ?? { type: 'drop' };
+
+	function changeDataInfo(dataInfo: DataInfo) {
+		setName(dataInfo.name);
+		setTabs(dataInfo.tabs ?? []);
+		setDatabase(dataInfo.isDatabase);
+		setCsvDialect(dataInfo.csvDialect);
+		setCustomQuery(
+			dataInfo.isDatabase && !dataInfo.tabs?.includes(dataInfo.sheetInfo.tabName ?? '')
+				? dataInfo.sheetInfo.tabName ?? ''
+				: ''
+		);
+		changeSheetInfo(dataInfo.sheetInfo);
+		setTrainSettings(dataInfo.trainSettings);
 	}
 
+	// Values out of range are rejected by start_train, which keeps the user on this page
+	function changeTrainSettings(change: Partial<TrainSettings>) {
+		setTrainSettings({ ...trainSettings, ...change });
+	}
+
+	// Re-read the whole CSV file with the dialect picked by user
+	const handleChangeDialect = useCallback(
+		(change: Partial<CsvDialect>) => {
+			if (disabled || !csvDialect) return;
+			setLoading(true);
+			invoke<DataInfo>('get_data_info', { csvDialect: { ...csvDialect, ...change } })
+				.then(changeDataInfo)
+				.finally(() => {
+					setLoading(false);
+				});
+		},
+		[disabled, csvDialect]
+	);
+
+	// Re-read the sheet with the date/time format picked by user, empty fields mean guessing
+	const handleApplyDatetimeFormat = useCallback(() => {
+		if (disabled) return;
+		setLoading(true);
+		invoke<SheetInfo>('select_sheet', {
+			tabName: selectedTab,
+			datetimeFormat: {
+				column: datetimeFormat.column ?? selectedDatetime,
+				format: datetimeFormat.format?.trim() || null,
+				timezone: datetimeFormat.timezone?.trim() || null,
+				numeric: datetimeFormat.numeric
+			}
+		})
+			.then(changeSheetInfo)
+			.finally(() => {
+				setLoading(false);
+			});
+	}, [disabled, selectedTab, selectedDatetime, datetimeFormat]);
+
+	// Re-read the worksheet with the header moved by user
+	const handleApplySheetLayout = useCallback(() => {
+		if (disabled) return;
+		setLoading(true);
+		invoke<SheetInfo>('select_sheet', { tabName: selectedTab, sheetLayout })
+			.then(changeSheetInfo)
+			.finally(() => {
+				setLoading(false);
+			});
+	}, [disabled, selectedTab, sheetLayout]);
+
+	// Re-read the sheet with the column type picked by user, 'auto' goes back to the majority
+	const handleOverrideColumnType = useCallback(
+		(event: SelectChangeEvent<string>) => {
+			if (disabled || !inspectedField) return;
+			let value = event.target.value;
+			setLoading(true);
+			invoke<SheetInfo>('override_column_type', {
+				tabName: selectedTab,
+				field: inspectedField,
+				columnType: value === 'auto' ? null : value
+			})
+				.then(changeSheetInfo)
+				.finally(() => {
+					setLoading(false);
+				});
+		},
+		[disabled, selectedTab, inspectedField]
+	);
+
+	// Re-read the sheet with missing cells of the inspected column filled, 'drop' goes back to dropping
+	const handleChangeMissingValue = useCallback(
+		(strategy: MissingValueStrategy) => {
+			if (disabled || !inspectedField) return;
+			setLoading(true);
+			invoke<SheetInfo>('set_missing_value_strategy', {
+				tabName: selectedTab,
+				field: inspectedField,
+				strategy: strategy.type === 'drop' ? null : strategy
+			})
+				.then(changeSheetInfo)
+				.finally(() => {
+					setLoading(false);
+				});
+		},
+		[disabled, selectedTab, inspectedField]
+	);
+
 	useEffect(() => {
 		setLoading(true);
 		setName('');
 		setTabs([]);
+		setDatabase(false);
+		setCsvDialect(null);
 		setColumns([]);
 		setRows([]); // Save memory on exit page
 		if (pageDisabled) return;
@@ -297,9 +617,7 @@ export default function PreprocessPage(props: BoxProps) {
 			timeoutHandler = NaN;
 			return invoke<DataInfo>('get_data_info').then((value) => {
 				if (isCanceled) return;
-				setName(value.name);
-				setTabs(value.tabs ?? []);
-				changeSheetInfo(value.sheetInfo);
+				changeDataInfo(value);
 				setLoading(false);
 			});
 		});
@@ -316,6 +634,9 @@ export default function PreprocessPage(props: BoxProps) {
 		setAllowedBatchPeriodes,
 		setSelectedDatetime,
 		setSelectedPredictable,
+		setSelectedTargets,
+		setSelectedFeatures,
+		setSelectedSeries,
 		setSelectedBatchPeriode,
 		setRowSelection,
 		setLoading
@@ -349,15 +670,21 @@ export default function PreprocessPage(props: BoxProps) {
 			tabName: selectedTab || undefined,
 			datetimeColumn: selectedDatetime,
 			predictableColumn: selectedPredictable,
+			targetColumns: selectedTargets.filter((each) => each !== selectedPredictable),
+			featureColumns: selectedFeatures.filter(
+				(each) => each !== selectedPredictable && !selectedTargets.includes(each)
+			),
+			seriesColumn: selectedSeries || undefined,
 			batchPeriode: selectedBatchPeriode,
-			rowSelection
+			rowSelection,
+			duplicateAggregation: selectedDuplicateAggregation
 		};
 
 		invoke<void>('submit_preprocess_config', { config })
 			.finally(() => {
 				setSubmitting(false);
 			})
-			.then(() => invoke<void>('start_train'));
+			.then(() => invoke<void>('start_train', { settings: trainSettings }));
 	}
 
 	return (
@@ -397,22 +724,363 @@ export default function PreprocessPage(props: BoxProps) {
 				>
 					Change
 				</Button>
-				<Collapse timeout={1000} in={!disabled && tabs.length > 0} unmountOnExit>
+				<Collapse
+					timeout={1000}
+					in={!disabled && (tabs.length > 0 || isDatabase)}
+					unmountOnExit
+				>
 					<Divider sx={defaultSx} />
 					<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
 						<InputLabel id="page-preprocess-label-tab" sx={inputLabelSx}>
-							Spreadsheet Tab
+							{isDatabase ? 'Database Table' : 'Spreadsheet Tab'}
 						</InputLabel>
 						<Select
 							labelId="page-preprocess-label-tab"
-							value={pageDisabled ? '' : selectedTab}
+							value={pageDisabled || !tabs.includes(selectedTab) ? '' : selectedTab}
 							onChange={handleSelectTab}
 						>
 							{tabSelections}
 						</Select>
 					</FormControl>
+					<Collapse in={isDatabase} unmountOnExit>
+						<TextField
+							fullWidth
+							multiline
+							label="Custom SQL Query"
+							placeholder="SELECT * FROM ..."
+							sx={defaultSx}
+							value={customQuery}
+							onChange={(e) => setCustomQuery(e.target.value)}
+							{...{ disabled }}
+						/>
+						<Button
+							variant="outlined"
+							sx={defaultSx}
+							disabled={disabled || !customQuery.trim()}
+							onClick={() => handleSelectTab({ target: { value: customQuery.trim() } })}
+						>
+							Run Query
+						</Button>
+					</Collapse>
+					<Collapse in={!isDatabase} unmountOnExit>
+						<TextField
+							fullWidth
+							type="number"
+							label="Header Row"
+							helperText="Counted from the first non-empty row, rows above it are skipped"
+							sx={defaultSx}
+							value={sheetLayout.headerRow + 1}
+							onChange={(e) =>
+								setSheetLayout({
+									...sheetLayout,
+									headerRow: Math.max(0, Math.floor(Number(e.target.value)) - 1 || 0)
+								})
+							}
+							{...{ disabled }}
+						/>
+						<TextField
+							fullWidth
+							type="number"
+							label="Header Row Count"
+							helperText="More than one if the header has several levels"
+							sx={defaultSx}
+							value={sheetLayout.headerRows}
+							onChange={(e) =>
+								setSheetLayout({
+									...sheetLayout,
+									headerRows: Math.max(1, Math.floor(Number(e.target.value)) || 1)
+								})
+							}
+							{...{ disabled }}
+						/>
+						<TextField
+							fullWidth
+							type="number"
+							label="Rows to Skip after Header"
+							sx={defaultSx}
+							value={sheetLayout.skipRows}
+							onChange={(e) =>
+								setSheetLayout({
+									...sheetLayout,
+									skipRows: Math.max(0, Math.floor(Number(e.target.value)) || 0)
+								})
+							}
+							{...{ disabled }}
+						/>
+						<Collapse in={sheetLayout.headerRows > 1} unmountOnExit>
+							<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+								<InputLabel id="page-preprocess-label-header-combine" sx={inputLabelSx}>
+									Combine Header Rows
+								</InputLabel>
+								<Select
+									labelId="page-preprocess-label-header-combine"
+									value={sheetLayout.headerCombine}
+									onChange={(e) =>
+										setSheetLayout({
+											...sheetLayout,
+											headerCombine: e.target.value as HeaderCombine
+										})
+									}
+								>
+									<MenuItem value="join">Join every row</MenuItem>
+									<MenuItem value="first">Topmost filled row</MenuItem>
+									<MenuItem value="last">Bottommost filled row</MenuItem>
+								</Select>
+							</FormControl>
+							<Collapse in={sheetLayout.headerCombine === 'join'} unmountOnExit>
+								<TextField
+									fullWidth
+									label="Header Separator"
+									sx={defaultSx}
+									value={sheetLayout.headerSeparator}
+									onChange={(e) =>
+										setSheetLayout({ ...sheetLayout, headerSeparator: e.target.value })
+									}
+									{...{ disabled }}
+								/>
+							</Collapse>
+						</Collapse>
+						<Button
+							variant="outlined"
+							sx={defaultSx}
+							{...{ disabled }}
+							onClick={handleApplySheetLayout}
+						>
+							Apply Header Position
+						</Button>
+					</Collapse>
+				</Collapse>
+				<Collapse timeout={1000} in={!disabled && !!csvDialect} unmountOnExit>
+					<Divider sx={defaultSx} />
+					<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+						<InputLabel id="page-preprocess-label-delimiter" sx={inputLabelSx}>
+							CSV Delimiter
+						</InputLabel>
+						<Select
+							labelId="page-preprocess-label-delimiter"
+							value={csvDialect?.delimiter ?? ''}
+							onChange={(e) => handleChangeDialect({ delimiter: e.target.value })}
+						>
+							{delimiterOptions.map((each) => (
+								<MenuItem key={each.value} value={each.value}>
+									{each.label}
+								</MenuItem>
+							))}
+						</Select>
+					</FormControl>
+					<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+						<InputLabel id="page-preprocess-label-encoding" sx={inputLabelSx}>
+							CSV Encoding
+						</InputLabel>
+						<Select
+							labelId="page-preprocess-label-encoding"
+							value={csvDialect?.encoding ?? ''}
+							onChange={(e) => handleChangeDialect({ encoding: e.target.value })}
+						>
+							{[
+								...encodingOptions,
+								...(csvDialect && !encodingOptions.includes(csvDialect.encoding)
+									? [csvDialect.encoding]
+									: [])
+							].map((each) => (
+								<MenuItem key={each} value={each}>
+									{each}
+								</MenuItem>
+							))}
+						</Select>
+					</FormControl>
+					<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+						<InputLabel id="page-preprocess-label-header" sx={inputLabelSx}>
+							CSV Header Row
+						</InputLabel>
+						<Select
+							labelId="page-preprocess-label-header"
+							value={csvDialect ? String(csvDialect.hasHeader) : ''}
+							onChange={(e) => handleChangeDialect({ hasHeader: e.target.value === 'true' })}
+						>
+							<MenuItem value="true">First row is the header</MenuItem>
+							<MenuItem value="false">No header, name columns by position</MenuItem>
+						</Select>
+					</FormControl>
+					<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+						<InputLabel id="page-preprocess-label-decimal" sx={inputLabelSx}>
+							CSV Number Format
+						</InputLabel>
+						<Select
+							labelId="page-preprocess-label-decimal"
+							value={csvDialect ? String(csvDialect.decimalComma) : ''}
+							onChange={(e) =>
+								handleChangeDialect({ decimalComma: e.target.value === 'true' })
+							}
+						>
+							<MenuItem value="false">1234.56 (decimal point)</MenuItem>
+							<MenuItem value="true">1.234,56 (decimal comma)</MenuItem>
+						</Select>
+					</FormControl>
 				</Collapse>
 				<Divider sx={defaultSx} />
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-inspect" sx={inputLabelSx}>
+						Inspect Column Type
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-inspect"
+						value={disabled || !inspectedColumn ? '' : inspectedField}
+						onChange={(e) => setInspectedField(e.target.value)}
+					>
+						{columns.map((each) => (
+							<MenuItem key={each.field} value={each.field}>
+								{each.headerName}
+							</MenuItem>
+						))}
+					</Select>
+				</FormControl>
+				<Collapse in={!disabled && !!inspectedColumn && !!inspectedInference} unmountOnExit>
+					<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+						<InputLabel id="page-preprocess-label-override" sx={inputLabelSx}>
+							Column Type
+						</InputLabel>
+						<Select
+							labelId="page-preprocess-label-override"
+							value={inspectedInference?.isOverridden ? inspectedColumn?.type ?? 'auto' : 'auto'}
+							onChange={handleOverrideColumnType}
+						>
+							<MenuItem value="auto">
+								Decided by majority ({inspectedColumn?.type ?? 'string'})
+							</MenuItem>
+							<MenuItem value="string">Text</MenuItem>
+							<MenuItem value="number">Number</MenuItem>
+							<MenuItem value="dateTime">Date/Time</MenuItem>
+							<MenuItem value="boolean">Boolean</MenuItem>
+						</Select>
+					</FormControl>
+					<Typography variant="body2" sx={defaultSx}>
+						{inspectedInference &&
+							`${inspectedInference.counts.string} text, ${inspectedInference.counts.number} number, ` +
+								`${inspectedInference.counts.datetime} date/time, ` +
+								`${inspectedInference.counts.boolean} boolean cells`}
+					</Typography>
+					<Typography variant="body2" sx={defaultSx}>
+						{inspectedInference && inspectedInference.offendingCount > 0
+							? `${inspectedInference.offendingCount} cells do not match, so ` +
+								(inspectedInference.isImputed ? 'they are filled, e.g. ' : 'their rows are dropped, e.g. ') +
+								inspectedInference.offendingSamples
+									.map((each) => `row ${each.rowId} "${each.value}"`)
+									.join(', ')
+							: 'Every cell matches this type'}
+					</Typography>
+					<Collapse in={inspectedColumn?.type === 'number'} unmountOnExit>
+						<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+							<InputLabel id="page-preprocess-label-missing" sx={inputLabelSx}>
+								Missing or Invalid Cells
+							</InputLabel>
+							<Select
+								labelId="page-preprocess-label-missing"
+								value={inspectedStrategy.type}
+								onChange={(e) => {
+									let type = e.target.value as MissingValueStrategy['type'];
+									handleChangeMissingValue(
+										type === 'constant' ? { type, value: Number(fillConstant) || 0 } : { type }
+									);
+								}}
+							>
+								<MenuItem value="drop">Drop the whole row</MenuItem>
+								<MenuItem value="forwardFill">Fill with the previous value</MenuItem>
+								<MenuItem value="backwardFill">Fill with the next value</MenuItem>
+								<MenuItem value="linear">Interpolate linearly</MenuItem>
+								<MenuItem value="mean">Fill with the mean of the column</MenuItem>
+								<MenuItem value="constant">Fill with a constant</MenuItem>
+							</Select>
+						</FormControl>
+						<Collapse in={inspectedStrategy.type === 'constant'} unmountOnExit>
+							<TextField
+								fullWidth
+								type="number"
+								label="Constant"
+								sx={defaultSx}
+								value={fillConstant}
+								onChange={(e) => setFillConstant(e.target.value)}
+								{...{ disabled }}
+							/>
+							<Button
+								variant="outlined"
+								sx={defaultSx}
+								{...{ disabled }}
+								onClick={() =>
+									handleChangeMissingValue({ type: 'constant', value: Number(fillConstant) || 0 })
+								}
+							>
+								Apply Constant
+							</Button>
+						</Collapse>
+					</Collapse>
+				</Collapse>
+				<Divider sx={defaultSx} />
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-format-column" sx={inputLabelSx}>
+						Column of Date/Time Format
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-format-column"
+						value={disabled ? '' : datetimeFormat.column ?? selectedDatetime}
+						onChange={(e) => setDatetimeFormat({ ...datetimeFormat, column: e.target.value })}
+					>
+						{columns.map((each) => (
+							<MenuItem key={each.field} value={each.field}>
+								{each.headerName}
+							</MenuItem>
+						))}
+					</Select>
+				</FormControl>
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-numeric" sx={inputLabelSx}>
+						Written As
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-numeric"
+						value={disabled ? '' : datetimeFormat.numeric ?? 'text'}
+						onChange={(e) =>
+							setDatetimeFormat({
+								...datetimeFormat,
+								numeric: e.target.value === 'text' ? null : (e.target.value as NumericTimestamp)
+							})
+						}
+					>
+						<MenuItem value="text">Text</MenuItem>
+						<MenuItem value="seconds">Unix epoch seconds</MenuItem>
+						<MenuItem value="milliseconds">Unix epoch milliseconds</MenuItem>
+						<MenuItem value="microseconds">Unix epoch microseconds</MenuItem>
+						<MenuItem value="excelSerial">Excel serial days</MenuItem>
+					</Select>
+				</FormControl>
+				<TextField
+					fullWidth
+					label="Date/Time Format"
+					placeholder="%Y-%m-%d %H:%M:%S"
+					helperText="strftime pattern, leave empty to guess"
+					sx={defaultSx}
+					value={datetimeFormat.format ?? ''}
+					onChange={(e) => setDatetimeFormat({ ...datetimeFormat, format: e.target.value })}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					label="Time Zone"
+					placeholder="UTC, Europe/Berlin, ..."
+					helperText="IANA time zone, leave empty to use the local one"
+					sx={defaultSx}
+					value={datetimeFormat.timezone ?? ''}
+					onChange={(e) => setDatetimeFormat({ ...datetimeFormat, timezone: e.target.value })}
+					{...{ disabled }}
+				/>
+				<Button
+					variant="outlined"
+					sx={defaultSx}
+					{...{ disabled }}
+					onClick={handleApplyDatetimeFormat}
+				>
+					Apply Date/Time Format
+				</Button>
 				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
 					<InputLabel id="page-preprocess-label-time" sx={inputLabelSx}>
 						Date/Time Column
@@ -441,6 +1109,52 @@ export default function PreprocessPage(props: BoxProps) {
 						{predictableSelections}
 					</Select>
 				</FormControl>
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-target" sx={inputLabelSx}>
+						Other Columns to Predict
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-target"
+						multiple
+						value={disabled ? [] : selectedTargets}
+						onChange={(e) => {
+							let value = e.target.value;
+							setSelectedTargets(typeof value === 'string' ? value.split(',') : value);
+						}}
+					>
+						{targetSelections}
+					</Select>
+				</FormControl>
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-feature" sx={inputLabelSx}>
+						Extra Input Columns
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-feature"
+						multiple
+						value={disabled ? [] : selectedFeatures}
+						onChange={(e) => {
+							let value = e.target.value;
+							setSelectedFeatures(typeof value === 'string' ? value.split(',') : value);
+						}}
+					>
+						{featureSelections}
+					</Select>
+				</FormControl>
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-series" sx={inputLabelSx}>
+						Series ID Column
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-series"
+						value={disabled ? '' : selectedSeries}
+						onChange={(e) => {
+							setSelectedSeries(e.target.value);
+						}}
+					>
+						{seriesSelections}
+					</Select>
+				</FormControl>
 				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
 					<InputLabel id="page-preprocess-label-batch" sx={inputLabelSx}>
 						Most Patterns Recurring...
@@ -455,6 +1169,171 @@ export default function PreprocessPage(props: BoxProps) {
 						{batchPeriode}
 					</Select>
 				</FormControl>
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-duplicate" sx={inputLabelSx}>
+						Rows Sharing a Timestamp
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-duplicate"
+						value={disabled ? '' : selectedDuplicateAggregation}
+						onChange={(e) => {
+							setSelectedDuplicateAggregation(e.target.value as DuplicateAggregation);
+						}}
+					>
+						<MenuItem value="mean">Merged by mean</MenuItem>
+						<MenuItem value="sum">Merged by sum</MenuItem>
+						<MenuItem value="first">Keep the first one</MenuItem>
+						<MenuItem value="last">Keep the last one</MenuItem>
+						<MenuItem value="min">Keep the minimum</MenuItem>
+						<MenuItem value="max">Keep the maximum</MenuItem>
+					</Select>
+				</FormControl>
+				<Divider sx={defaultSx} />
+				<Typography variant="body1" sx={defaultSx}>
+					Training settings:
+				</Typography>
+				<TextField
+					fullWidth
+					type="number"
+					label="Hidden Size"
+					sx={defaultSx}
+					value={trainSettings.hiddenSize}
+					onChange={(e) =>
+						changeTrainSettings({
+							hiddenSize: Math.max(0, Math.floor(Number(e.target.value)) || 0)
+						})
+					}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Layer Count"
+					sx={defaultSx}
+					value={trainSettings.numLayers}
+					onChange={(e) =>
+						changeTrainSettings({ numLayers: Math.max(0, Math.floor(Number(e.target.value)) || 0) })
+					}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Dropout"
+					helperText="Between 0 and 1, only applied between layers"
+					sx={defaultSx}
+					value={trainSettings.dropout}
+					onChange={(e) => changeTrainSettings({ dropout: Number(e.target.value) || 0 })}
+					{...{ disabled }}
+				/>
+				<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+					<InputLabel id="page-preprocess-label-bidirectional" sx={inputLabelSx}>
+						Direction
+					</InputLabel>
+					<Select
+						labelId="page-preprocess-label-bidirectional"
+						value={String(trainSettings.bidirectional)}
+						onChange={(e) => changeTrainSettings({ bidirectional: e.target.value === 'true' })}
+					>
+						<MenuItem value="false">Forward only</MenuItem>
+						<MenuItem value="true">Bidirectional</MenuItem>
+					</Select>
+				</FormControl>
+				<TextField
+					fullWidth
+					type="number"
+					label="Learning Rate"
+					sx={defaultSx}
+					value={trainSettings.learningRate}
+					onChange={(e) => changeTrainSettings({ learningRate: Number(e.target.value) || 0 })}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Batch Size"
+					helperText="Sequences per optimizer step"
+					sx={defaultSx}
+					value={trainSettings.batchSize}
+					onChange={(e) =>
+						changeTrainSettings({ batchSize: Math.max(0, Math.floor(Number(e.target.value)) || 0) })
+					}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Maximum Epochs"
+					sx={defaultSx}
+					value={trainSettings.maxEpochs}
+					onChange={(e) =>
+						changeTrainSettings({ maxEpochs: Math.max(0, Math.floor(Number(e.target.value)) || 0) })
+					}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Gradient Clipping"
+					helperText="Maximum gradient norm, leave empty to disable"
+					sx={defaultSx}
+					value={trainSettings.clipNorm ?? ''}
+					onChange={(e) =>
+						changeTrainSettings({
+							clipNorm: e.target.value.trim() ? Number(e.target.value) || 0 : null
+						})
+					}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Patience"
+					helperText="Stops after this many epochs without improvement..."
+					sx={defaultSx}
+					value={trainSettings.patience}
+					onChange={(e) =>
+						changeTrainSettings({ patience: Math.max(0, Math.floor(Number(e.target.value)) || 0) })
+					}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Minimum Improvement"
+					helperText="...which means validation loss drops by more than this..."
+					sx={defaultSx}
+					value={trainSettings.minDelta}
+					onChange={(e) => changeTrainSettings({ minDelta: Number(e.target.value) || 0 })}
+					{...{ disabled }}
+				/>
+				<TextField
+					fullWidth
+					type="number"
+					label="Minimum Epochs"
+					helperText="...but not before this epoch"
+					sx={defaultSx}
+					value={trainSettings.minEpochs}
+					onChange={(e) =>
+						changeTrainSettings({ minEpochs: Math.max(0, Math.floor(Number(e.target.value)) || 0) })
+					}
+					{...{ disabled }}
+				/>
+				<Collapse in={!disabled && !!selectedSeries} unmountOnExit>
+					<FormControl fullWidth sx={defaultSx} {...{ disabled }}>
+						<InputLabel id="page-preprocess-label-embedding" sx={inputLabelSx}>
+							Series Embedding
+						</InputLabel>
+						<Select
+							labelId="page-preprocess-label-embedding"
+							value={String(trainSettings.seriesEmbedding)}
+							onChange={(e) => changeTrainSettings({ seriesEmbedding: e.target.value === 'true' })}
+						>
+							<MenuItem value="false">Learn every series alike</MenuItem>
+							<MenuItem value="true">Tell each series apart</MenuItem>
+						</Select>
+					</FormControl>
+				</Collapse>
 			</Box>
 			<Box
 				sx={{
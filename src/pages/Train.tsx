@@ -18,7 +18,7 @@
 
 import type { BoxProps } from '@mui/material/Box';
 
-import { useState, useReducer, useMemo, useEffect } from 'react';
+import { Fragment, useState, useReducer, useMemo, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 
@@ -26,42 +26,82 @@ import Box from '@mui/material/Box';
 import CircularProgress from '@mui/material/CircularProgress';
 import Typography from '@mui/material/Typography';
 import Skeleton from '@mui/material/Skeleton';
+import Button from '@mui/material/Button';
 
 import { LineChart } from '@mui/x-charts/LineChart';
 
+import PauseIcon from '@mui/icons-material/Pause';
+import ResumeIcon from '@mui/icons-material/PlayArrow';
+import StopIcon from '@mui/icons-material/Stop';
+import DiscardIcon from '@mui/icons-material/Close';
+
+type ForecastMetrics = {
+	mae: number;
+	rmse: number;
+	mape: number | null;
+	smape: number | null;
+	r2: number | null;
+};
+
+type ColumnMetrics = {
+	column: string;
+	metrics: ForecastMetrics;
+};
+
 type Point = {
 	x: number;
-	y: number;
+	metrics: ColumnMetrics[];
 };
 
-type ConfidenceGraphAction =
+type MetricGraphAction =
 	| { type: 'set'; value: Point[] }
 	| { type: 'add'; value: Point }
 	| { type: 'clear' };
 
+interface TrainSettings {
+	hiddenSize: number;
+	numLayers: number;
+	dropout: number;
+	bidirectional: boolean;
+	learningRate: number;
+	batchSize: number;
+	maxEpochs: number;
+	clipNorm: number | null;
+	patience: number;
+	minDelta: number;
+	minEpochs: number;
+	seriesEmbedding: boolean;
+}
+
+type StopReason = 'maxEpochs' | 'noImprovement' | 'canceled';
+
 interface TrainProgress {
 	endX: number;
-	confidencePoints: Point[];
+	epochPoints: Point[];
+	settings: TrainSettings;
+	stopReason: StopReason | null;
+	bestEpoch: number;
+	paused: boolean;
 }
 
 export default function TrainPage(props: BoxProps) {
 	const [endX, setEndX] = useState(0);
+	const [isPaused, setPaused] = useState(false);
+	const [isStopping, setStopping] = useState(false);
+	const [settings, setSettings] = useState<TrainSettings | null>(null);
 
-	const [confidencePoints, modifyConfidenceGraph] = useReducer(
-		(state: Point[], action: ConfidenceGraphAction) => {
+	const [epochPoints, modifyMetricGraph] = useReducer(
+		(state: Point[], action: MetricGraphAction) => {
 			switch (action.type) {
 				case 'add':
-					if (state.length === 1 && state[0].x === 0 && action.value.x === 0)
-						return [action.value];
-					else return [...state, action.value];
-				// @ts-ignore 7029: intended to fallthrough
+					return [...state, action.value];
 				case 'set':
-					if (action.value.length > 0) return action.value;
+					return action.value;
 				case 'clear':
-					return [{ x: 0, y: 0 }];
+					return [];
 			}
 		},
-		[{ x: 0, y: 0 }]
+		[]
 	);
 
 	const pageDisabled = useMemo(
@@ -69,7 +109,8 @@ export default function TrainPage(props: BoxProps) {
 		[props.tabIndex]
 	);
 
-	const confidenceGraph = useMemo(
+	// Validation RMSE of the predictable column, which is in the same unit as its data
+	const rmseGraph = useMemo(
 		() =>
 			pageDisabled ? (
 				<Skeleton sx={{ width: '100%', height: '100%' }} />
@@ -77,29 +118,61 @@ export default function TrainPage(props: BoxProps) {
 				<LineChart
 					sx={{ width: '100%', height: '100%', minHeight: '0' }}
 					xAxis={[{ dataKey: 'x', min: 0, max: endX, label: 'Training Epoch' }]}
-					yAxis={[{ min: 0, max: 100, label: 'Confidence (%)' }]}
-					series={[{ dataKey: 'y', area: true, showMark: false }]}
+					yAxis={[
+						{
+							min: 0,
+							label: `Validation RMSE${
+								epochPoints[0]?.metrics[0] ? ` of ${epochPoints[0].metrics[0].column}` : ''
+							}`
+						}
+					]}
+					series={[{ dataKey: 'rmse', area: true, showMark: false }]}
 					fillOpacity="0.5"
 					grid={{ horizontal: true, vertical: true }}
 					skipAnimation
-					dataset={confidencePoints}
+					dataset={epochPoints.map((each) => ({
+						x: each.x,
+						rmse: each.metrics[0]?.metrics.rmse ?? null
+					}))}
 				/>
 			),
-		[pageDisabled, confidencePoints, endX]
+		[pageDisabled, epochPoints, endX]
 	);
 
-	const latestConfidence = useMemo<number>(
+	// Effective settings, so the user knows what the model is trained with
+	const settingsSummary = useMemo(
 		() =>
-			confidencePoints.length > 0
-				? Math.round((confidencePoints[confidencePoints.length - 1]?.y ?? 0) * 100) / 100
-				: 0,
-		[confidencePoints.length]
+			settings
+				? [
+						`${settings.numLayers} ${settings.bidirectional ? 'bidirectional ' : ''}` +
+							`layer(s) of ${settings.hiddenSize} units`,
+						`dropout ${settings.dropout}`,
+						`learning rate ${settings.learningRate}`,
+						`batch size ${settings.batchSize}`,
+						settings.clipNorm === null
+							? 'no gradient clipping'
+							: `gradient clipped at ${settings.clipNorm}`,
+						`patience ${settings.patience} epochs after epoch ${settings.minEpochs}` +
+							` (minimum improvement ${settings.minDelta})`,
+						...(settings.seriesEmbedding ? ['series embedding'] : [])
+				  ].join(', ')
+				: '',
+		[settings]
+	);
+
+	const latestMetrics = useMemo(
+		() => epochPoints[epochPoints.length - 1]?.metrics,
+		[epochPoints.length]
 	);
 
 	useEffect(() => {
+		setPaused(false);
+		setStopping(false);
+
 		if (pageDisabled) {
 			setEndX(0);
-			modifyConfidenceGraph({ type: 'clear' });
+			setSettings(null);
+			modifyMetricGraph({ type: 'clear' });
 			return;
 		}
 
@@ -108,18 +181,32 @@ export default function TrainPage(props: BoxProps) {
 		invoke<TrainProgress>('get_train_progress').then((value) => {
 			if (isCanceled) return;
 			setEndX(value.endX);
-			modifyConfidenceGraph({ type: 'set', value: value.confidencePoints });
+			setPaused(value.paused);
+			setSettings(value.settings);
+			modifyMetricGraph({ type: 'set', value: value.epochPoints });
 		});
 
 		let unlistener = listen<Point>('App://train/progress/new', (e) => {
-			modifyConfidenceGraph({ type: 'add', value: e.payload });
+			modifyMetricGraph({ type: 'add', value: e.payload });
 		});
 
 		return () => {
 			isCanceled = true;
 			unlistener.then((unlisten) => unlisten());
 		};
-	}, [pageDisabled, setEndX, modifyConfidenceGraph]);
+	}, [pageDisabled, setEndX, setPaused, setStopping, setSettings, modifyMetricGraph]);
+
+	const handlePauseResume = useCallback(() => {
+		invoke<void>(isPaused ? 'resume_train' : 'pause_train').then(() => setPaused(!isPaused));
+	}, [isPaused, setPaused]);
+
+	const handleCancel = useCallback(
+		(keepModel: boolean) => {
+			setStopping(true);
+			invoke<void>('cancel_train', { keepModel }).catch(() => setStopping(false));
+		},
+		[setStopping]
+	);
 
 	return (
 		<Box
@@ -145,19 +232,66 @@ export default function TrainPage(props: BoxProps) {
 			>
 				<CircularProgress variant="indeterminate" size={32} />
 				<Typography variant="h6" component="h1">
-					Understanding historical data...
+					{isPaused ? 'Paused' : 'Understanding historical data...'}
 				</Typography>
 			</Box>
-			<Box sx={{ flex: 1, overflow: 'hidden' }}>{confidenceGraph}</Box>
+			<Typography variant="body2" sx={{ textAlign: 'center' }}>
+				{settingsSummary ? `Trained with ${settingsSummary}` : <Skeleton variant="text" />}
+			</Typography>
+			<Box sx={{ flex: 1, overflow: 'hidden' }}>{rmseGraph}</Box>
 			<Typography variant="body1" sx={{ textAlign: 'center' }}>
-				{latestConfidence ? (
+				{latestMetrics && latestMetrics.length > 0 ? (
 					<>
-						Prediction confidence: <strong>{latestConfidence.toString()}%</strong>
+						Validation RMSE of{' '}
+						{latestMetrics.map((each, index) => (
+							<Fragment key={each.column}>
+								{index > 0 ? ', ' : ''}
+								{each.column}:{' '}
+								<strong>{(Math.round(each.metrics.rmse * 10000) / 10000).toString()}</strong>
+							</Fragment>
+						))}
 					</>
 				) : (
 					<>☕ Brew coffee and enjoy... This will take quite amount of time</>
 				)}
 			</Typography>
+			<Box
+				sx={{
+					display: 'flex',
+					flexDirection: 'row',
+					justifyContent: 'center',
+					alignItems: 'center',
+					gap: '16px'
+				}}
+			>
+				<Button
+					variant="outlined"
+					color="secondary"
+					startIcon={isPaused ? <ResumeIcon /> : <PauseIcon />}
+					onClick={handlePauseResume}
+					disabled={pageDisabled || isStopping}
+				>
+					{isPaused ? 'Resume' : 'Pause'}
+				</Button>
+				<Button
+					variant="outlined"
+					color="secondary"
+					startIcon={<StopIcon />}
+					onClick={() => handleCancel(true)}
+					disabled={pageDisabled || isStopping}
+				>
+					Stop and Evaluate
+				</Button>
+				<Button
+					variant="outlined"
+					color="secondary"
+					startIcon={<DiscardIcon />}
+					onClick={() => handleCancel(false)}
+					disabled={pageDisabled || isStopping}
+				>
+					Cancel
+				</Button>
+			</Box>
 		</Box>
 	);
 }